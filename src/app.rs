@@ -1,17 +1,42 @@
 use self::State::*;
-use crate::scramble::Scramble;
+use crate::clock::{Clock, SystemClock};
+use crate::config::Config;
+use crate::cube::Cube;
+use crate::entry;
+use crate::hints::{self, Hint};
+use crate::input::{InputMode, RepeatTracker};
+use crate::inspection::Inspection;
+use crate::mbld::{self, Mbld, MbldResult};
+use crate::reconstruction::Reconstruction;
+use crate::schedule;
+use crate::scramble::{Scramble, Variant};
+use crate::scramble_set::ScrambleSet;
+use crate::solver;
+use crate::subset::Subset;
 use crate::timer::Timer;
 use crate::times::Times;
+use crate::trainer::Trainer;
+use crate::turns::{Layers, Move};
+use crate::virtual_cube::VirtualCube;
 
 use figlet_rs::FIGfont;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use serde_json::{json, Value};
+use std::error;
 use std::fmt::Display;
-use std::{error, time::Duration};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tui::layout::Rect;
 use tui::style::Color;
 use tui::widgets::TableState;
 
 /// Application result type.
 pub type AppResult<T> = std::result::Result<T, Box<dyn error::Error>>;
 
+/// Warning shown while timing out of focus, until focus comes back.
+const FOCUS_LOST: &str = "The terminal lost focus while timing, focus it again to stop the timer";
+
 /// Application.
 #[derive(Debug)]
 pub struct App {
@@ -23,20 +48,71 @@ pub struct App {
     pub time: Duration,    // current time
     pub time_color: Color, // color of the main time
     pub scramble: Scramble,
-    pub inspection_timer: Timer,   // timer for inspection
-    pub inspection_time: Duration, // current inspection time
-    pub times: Times,              // times list
-    pub show_last_scramble: bool,  // bool to determine whether to show last scramble
-    pub table_state: TableState,   // todo!()
-    pub penalty: Penalty,          // penalty type
+    pub inspection_timer: Timer,              // timer for inspection
+    pub inspection_time: Duration,            // current inspection time
+    pub times: Times,                         // times list
+    pub show_last_scramble: bool,             // bool to determine whether to show last scramble
+    pub table_state: TableState,              // todo!()
+    pub penalty: Penalty,                     // penalty type
+    pub variant: Variant,                     // event variant the session is scrambled for
+    pub seed: u64,                            // seed the session's scrambles are generated from
+    pub rng: ChaCha8Rng,                      // scramble generator, seeded with `seed`
+    pub show_net: bool, // bool to determine whether to show the scrambled cube net
+    pub config: Config, // user settings
+    pub scramble_set: Option<ScrambleSet>, // pre-generated scrambles to use before generating new ones
+    pub subset: Subset,                    // subset the session drills, for 3x3 variants
+    pub case: Option<String>,              // last layer case the current scramble sets up
+    pub generating: bool,                  // scramble waiting for the solver's tables
+    pub mode: Mode,                        // whether full solves or algorithm cases are timed
+    pub trainer: Option<Trainer>,          // algorithm cases, loaded when first trained
+    pub message: Option<String>,           // error or notice shown until the next key press
+    pub detail: Option<usize>,             // index of the solve shown in the detail popup
+    pub hints: Option<Vec<Hint>>,          // first step hints for that solve
+    pub searching: bool,                   // hints waiting for their tables
+    pub virtual_cube: Option<VirtualCube>, // cube turned from the keyboard in virtual mode
+    pub playback: usize,                   // reconstruction moves shown on the detail net
+    pub reconstruction_input: Option<String>, // reconstruction being typed
+    pub time_input: Option<String>,        // time being typed in for the scramble
+    pub mbld: Option<Mbld>,                // multi-blind attempt, in multi-blind mode
+    pub inspection: Inspection,            // inspection the session uses for the variant
+    pub warning: Option<u64>,              // last inspection warning reached, in seconds
+    pub bell: bool,                        // terminal bell to ring on the next draw
+    pub held_since: Option<Instant>,       // when space was pressed to get ready
+    pub input: InputMode,                  // how space is read from the terminal
+    pub focused: bool,                     // whether the terminal has focus, as far as it says
+    pub repeat: RepeatTracker,             // space key repeats, in repeat input mode
+    pub clock: Arc<dyn Clock>,             // where the time comes from, shared with the timers
+    pub areas: Areas,                      // where the parts that can be clicked were last drawn
+}
+
+// Whether the timer times full solves, algorithm cases, solves of the virtual
+// cube or multi-blind attempts
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub enum Mode {
+    Timer,
+    Trainer,
+    Virtual,
+    Mbld,
 }
 
-// App state
-#[derive(Debug, PartialEq)]
+// App state, moving like a stackmat: space is held until the timer is ready,
+// and the timer starts when it is let go
+#[derive(Debug, PartialEq, Copy, Clone)]
 pub enum State {
-    Idle,
-    Timing,
-    Inspecting,
+    Idle,       // waiting for the next solve
+    Inspecting, // inspecting the scramble
+    Holding,    // space held, not yet for long enough to start
+    Ready,      // space held long enough, letting go starts
+    Timing,     // timing a solve
+    Stopped,    // timer stopped, waiting for space to be let go
+}
+
+/// Parts of the screen that can be clicked, as last drawn.
+#[derive(Debug, Default, Clone)]
+pub struct Areas {
+    pub scramble: Rect,                  // scramble block, clicked for a new scramble
+    pub times: Rect,                     // rows of the times table, below its header
+    pub penalties: Vec<(Penalty, Rect)>, // penalty buttons of the detail popup
 }
 
 // Penalty types
@@ -60,29 +136,82 @@ impl Display for Penalty {
 // Default implementation for App
 impl Default for App {
     fn default() -> Self {
-        Self {
+        Self::with_seed(rand::thread_rng().gen())
+    }
+}
+
+impl App {
+    /// Constructs a new instance of [`App`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Constructs a new instance of [`App`] whose scrambles are generated from `seed`,
+    /// so the same seed always gives the same scramble sequence.
+    pub fn with_seed(seed: u64) -> Self {
+        Self::with_times(seed, Times::new(), Config::load())
+    }
+
+    /// Constructs a new instance of [`App`] around the given times and settings,
+    /// e.g. an in-memory session for tests.
+    pub fn with_times(seed: u64, times: Times, config: Config) -> Self {
+        Self::with_clock(seed, times, config, Arc::new(SystemClock))
+    }
+
+    /// Constructs a new instance of [`App`] timed by `clock`, e.g. a manual
+    /// clock to test timing without waiting.
+    pub fn with_clock(seed: u64, times: Times, config: Config, clock: Arc<dyn Clock>) -> Self {
+        let variant = times.variant();
+        let subset = times.subset();
+        let inspection = times.inspection(variant);
+        let message = (!config.errors.is_empty()).then(|| config.errors.join("; "));
+        let mut app = Self {
             state: Idle,
             running: true,
             show_help: false,
             font: FIGfont::standard().unwrap(),
-            timer: Timer::new(),
+            timer: Timer::with_clock(clock.clone()),
             time: Duration::new(0, 0),
             time_color: Color::White,
-            scramble: Scramble::new(20),
-            inspection_timer: Timer::new(),
+            scramble: Scramble { moves: vec![] },
+            inspection_timer: Timer::with_clock(clock.clone()),
             inspection_time: Duration::new(0, 0),
-            times: Times::new(),
+            times,
             show_last_scramble: false,
             table_state: TableState::default(),
             penalty: Penalty::None,
-        }
-    }
-}
-
-impl App {
-    /// Constructs a new instance of [`App`].
-    pub fn new() -> Self {
-        Self::default()
+            variant,
+            seed,
+            rng: ChaCha8Rng::seed_from_u64(seed),
+            show_net: false,
+            config,
+            scramble_set: None,
+            subset,
+            case: None,
+            generating: false,
+            mode: Mode::Timer,
+            trainer: None,
+            message,
+            detail: None,
+            hints: None,
+            searching: false,
+            virtual_cube: None,
+            playback: 0,
+            reconstruction_input: None,
+            time_input: None,
+            mbld: None,
+            inspection,
+            warning: None,
+            bell: false,
+            held_since: None,
+            input: InputMode::Release,
+            focused: true,
+            repeat: RepeatTracker::default(),
+            clock,
+            areas: Areas::default(),
+        };
+        app.new_scramble();
+        app
     }
 
     /// Handles the tick event of the terminal.
@@ -90,6 +219,73 @@ impl App {
         // Refresh the time and inspection time every tick.
         self.time = self.timer.get_time();
         self.inspection_time = self.inspection_timer.get_time();
+        self.inspection_step(self.inspection_time);
+        self.input_step(self.clock.now());
+        self.mbld_step(self.clock.now());
+        if self.generating && solver::ready() {
+            self.new_scramble();
+        }
+        if self.searching && hints::ready() {
+            self.find_hints();
+        }
+    }
+
+    /// Moves on from holding space as time passes, which depends on whether
+    /// the terminal reports space being let go.
+    pub fn input_step(&mut self, now: Instant) {
+        match self.input {
+            InputMode::Release | InputMode::Evdev => self.hold_step(now),
+            // a hold is only known from its repeats
+            InputMode::Arm => {}
+            InputMode::Repeat => {
+                if let Some(at) = self.repeat.released(now) {
+                    self.release_space(at);
+                }
+            }
+        }
+    }
+
+    /// Turns the timer ready once space has been held for the configured time.
+    pub fn hold_step(&mut self, now: Instant) {
+        if let (Holding, Some(since)) = (self.state, self.held_since) {
+            if now.saturating_duration_since(since) >= self.config.hold {
+                self.state = Ready;
+                self.time_color = Color::Green;
+            }
+        }
+    }
+
+    /// Stops a multi-blind attempt once its time limit is up.
+    pub fn mbld_step(&mut self, now: Instant) {
+        let Some(limit) = self.mbld.as_ref().map(Mbld::time_limit) else {
+            return;
+        };
+        if self.state == Timing && self.timer.time_at(now) >= limit {
+            self.press_space(now);
+        }
+    }
+
+    /// Updates the inspection penalty and warnings after inspecting for
+    /// `elapsed`. The penalty is only shown here; it is applied to the solve
+    /// when it starts.
+    pub fn inspection_step(&mut self, elapsed: Duration) {
+        if !self.inspection_timer.running {
+            self.warning = None;
+            return;
+        }
+        self.penalty = self.inspection.penalty(elapsed);
+        if self.penalty != Penalty::None && self.state == Inspecting {
+            self.time_color = Color::Red;
+        }
+
+        // Call out inspection warnings once each, ringing the bell if asked to.
+        let warning = self.inspection.warning(elapsed);
+        if warning != self.warning {
+            self.warning = warning;
+            if warning.is_some() && self.config.inspection_bell {
+                self.bell = true;
+            }
+        }
     }
 
     /// Set running to false to quit the application.
@@ -107,65 +303,297 @@ impl App {
         }
     }
 
-    /// Handles and determines what happens when the user clicks space.
-    /// Does the timer start?, stop?, reset?, etc.
-    pub fn handle_space(&mut self) {
+    /// Handles space being pressed at `at`: getting ready to start, or ending
+    /// a phase or the solve while timing.
+    pub fn press_space(&mut self, at: Instant) {
         match self.state {
-            Idle => {
-                // if idle, start inspection, and reset so there is no penalty
-                self.start_inspection();
-                self.penalty = Penalty::None;
+            // nothing to solve until the scramble is generated
+            Idle if self.generating => {}
+            Idle | Inspecting => {
+                // hold until ready, inspection keeps running meanwhile
+                self.state = Holding;
+                self.held_since = Some(at);
+                self.time_color = Color::Red;
+                self.show_help = false;
             }
-            Inspecting => {
-                // if inspecting, stop inspecting, and start timing
-                self.stop_inspection();
-                self.state = Timing;
-                self.timer.reset();
-                self.timer.start();
+            Timing if self.mode == Mode::Trainer => {
+                // if training, add the time to the case instead of the times list
+                self.time = self.timer.stop_at(at);
+                self.state = Stopped;
+                if let Some(trainer) = self.trainer.as_mut() {
+                    trainer.last = trainer.current;
+                    trainer.undo = None;
+                    trainer.missable = trainer.current.is_some();
+                    if let Some(case) = trainer.current {
+                        // grade against the average before this attempt
+                        let name = &trainer.cases[case].name;
+                        let time = self.time.as_secs_f64();
+                        let stats = self.times.trainer_stats(name);
+                        let average = (stats.count > 0).then_some(stats.mean);
+                        let now = schedule::now();
+                        let scheduled = self.times.schedule(name);
+                        let previous = scheduled.unwrap_or_default();
+                        // drilling a case before it is due is extra practice,
+                        // only due and new cases are reviewed
+                        if scheduled.map_or(true, |s| s.is_due(now)) {
+                            let grade = schedule::grade(time, average);
+                            self.times.set_schedule(name, previous.review(grade, now));
+                            trainer.undo = Some(previous);
+                        }
+                        self.times.add_trainer_time(name, time);
+                    }
+                }
+                self.new_scramble();
+            }
+            Timing if self.mode == Mode::Mbld => {
+                // ask how many cubes were solved, counting no further than
+                // the time limit
+                self.time = self.timer.stop_at(at);
+                self.state = Stopped;
+                if let Some(mbld) = self.mbld.as_mut() {
+                    self.time = self.time.min(mbld.time_limit());
+                    mbld.time = Some(self.time.as_secs_f64());
+                    mbld.input = Some(String::new());
+                }
+            }
+            Timing if self.timer.splits.len() + 1 < self.config.phases.len() => {
+                // end of a phase before the last, record a split and keep timing
+                self.timer.split_at(at);
             }
             Timing => {
                 // if timing, stop timing, and add time to times list
-                self.timer.stop();
-                self.state = Idle;
+                self.time = self.timer.stop_at(at);
+                self.state = Stopped;
                 self.times.add_time(
                     self.time.as_secs_f64(),
                     self.scramble.to_string(),
                     self.penalty,
                 );
-                self.scramble = Scramble::new(20); // reset the scramble
+                if self.config.phases.len() > 1 {
+                    self.times.set_last("splits", self.splits());
+                }
+                self.record_drill();
+                self.new_scramble(); // reset the scramble
+            }
+            // held down, e.g. key repeats
+            Holding | Ready | Stopped => {}
+        }
+    }
+
+    /// Handles space being let go at `at`: starting inspection or the timer if
+    /// it was held long enough, going back to waiting if not.
+    pub fn release_space(&mut self, at: Instant) {
+        self.hold_step(at);
+        match self.state {
+            Ready if self.inspection_timer.running => {
+                // if inspecting, stop inspecting, and start timing with the
+                // penalty for the time inspected
+                self.penalty = self.inspection.penalty(self.inspection_timer.time_at(at));
+                self.stop_inspection();
+                self.start_timing(at);
+            }
+            Ready
+                if matches!(self.mode, Mode::Trainer | Mode::Mbld)
+                    || self.inspection == Inspection::None =>
+            {
+                // algorithm cases, multi-blind attempts and sessions without
+                // inspection start straight away
+                self.penalty = Penalty::None;
+                self.start_timing(at);
+            }
+            Ready => {
+                // start inspection, and reset so there is no penalty
+                self.start_inspection(at);
+                self.penalty = Penalty::None;
+            }
+            Holding => {
+                // let go too early, carry on as before
+                self.state = match self.inspection_timer.running {
+                    true => Inspecting,
+                    false => Idle,
+                };
+            }
+            Stopped => self.state = Idle,
+            Idle | Inspecting | Timing => return,
+        }
+        self.held_since = None;
+        self.time_color = Color::White;
+    }
+
+    /// Handles a press of space in arm input mode: the first press gets the
+    /// timer ready, the next starts it, and a press while timing stops it.
+    pub fn arm_space(&mut self, at: Instant) {
+        match self.state {
+            Idle | Inspecting => {
+                self.press_space(at);
+                self.state = Ready;
+                self.time_color = Color::Green;
+            }
+            Ready => self.release_space(at),
+            Timing => {
+                self.press_space(at);
+                self.release_space(at);
+            }
+            Holding | Stopped => {}
+        }
+    }
+
+    /// Handles a press of space in repeat input mode, where holding space
+    /// repeats the press until it is let go.
+    pub fn repeat_space(&mut self, at: Instant) {
+        self.input_step(at);
+        if self.repeat.press(at) {
+            self.press_space(at);
+        } else {
+            self.hold_step(at);
+        }
+    }
+
+    /// Handles the terminal losing focus, after which space isn't seen, even
+    /// when read from the keyboard directly: a hold is let go of, and a solve
+    /// being timed carries on with a warning.
+    pub fn focus_lost(&mut self) {
+        self.focused = false;
+        match self.state {
+            Holding | Ready => {
+                self.state = match self.inspection_timer.running {
+                    true => Inspecting,
+                    false => Idle,
+                };
+                self.held_since = None;
+                self.time_color = Color::White;
             }
+            Timing => self.message = Some(FOCUS_LOST.to_string()),
+            Stopped => self.state = Idle,
+            Idle | Inspecting => {}
+        }
+    }
+
+    /// Handles the terminal getting focus back, when its warning is no
+    /// longer needed.
+    pub fn focus_gained(&mut self) {
+        self.focused = true;
+        if self.message.as_deref() == Some(FOCUS_LOST) {
+            self.message = None;
+        }
+    }
+
+    /// Handles text pasted into the terminal, which goes into a reconstruction
+    /// or time being typed.
+    pub fn paste(&mut self, text: &str) {
+        if let Some(input) = self.reconstruction_input.as_mut() {
+            input.push_str(&text.replace("\r\n", "\n").replace('\r', "\n"));
+        }
+        if let Some(input) = self.time_input.as_mut() {
+            input.push_str(text.trim());
+        }
+    }
+
+    /// Start or stop typing in a time for the current scramble.
+    pub fn toggle_time_entry(&mut self) {
+        self.time_input = match self.time_input {
+            Some(_) => None,
+            None => Some(String::new()),
+        };
+    }
+
+    /// Add the time typed in with the current scramble, or say why it can't be.
+    pub fn enter_time(&mut self) {
+        let Some(input) = &self.time_input else {
+            return;
+        };
+        match entry::parse_time(input) {
+            Ok((time, penalty)) => {
+                self.time_input = None;
+                self.times
+                    .add_time(time, self.scramble.to_string(), penalty);
+                self.record_drill();
+                self.time = Duration::from_secs_f64(time);
+                self.new_scramble();
+            }
+            Err(e) => self.message = Some(e),
+        }
+    }
+
+    /// Record the subset and case the last solve drilled, if any.
+    fn record_drill(&mut self) {
+        if self.drills_subset() {
+            self.times.set_last("subset", self.subset.id().into());
+            if let Some(case) = &self.case {
+                self.times.set_last("case", case.as_str().into());
+            }
+        }
+    }
+
+    /// Start timing a solve from `at`.
+    fn start_timing(&mut self, at: Instant) {
+        self.state = Timing;
+        self.timer.reset();
+        self.timer.start_at(at);
+    }
+
+    /// Time of each phase of the last solve, as stored with it:
+    /// `[{"phase": "Cross", "time": 1.52}, ..]`.
+    pub fn splits(&self) -> Value {
+        let mut ends = self.timer.splits.clone();
+        ends.push(self.time);
+        let mut start = Duration::new(0, 0);
+        let splits: Vec<Value> = self
+            .config
+            .phases
+            .iter()
+            .zip(ends)
+            .map(|(phase, end)| {
+                let time = end.saturating_sub(start);
+                start = end;
+                json!({ "phase": phase, "time": time.as_secs_f64() })
+            })
+            .collect();
+        Value::from(splits)
+    }
+
+    /// Phase being timed, while timing a solve split into phases.
+    pub fn current_phase(&self) -> Option<&str> {
+        match self.state {
+            Timing if self.mode == Mode::Timer => self
+                .config
+                .phases
+                .get(self.timer.splits.len())
+                .map(String::as_str),
+            _ => None,
         }
     }
 
     /// Determines what time should be displayed on the main timer.
-    pub fn time_string(&mut self) -> String {
-        if self.state == Inspecting {
-            if (15.00 - self.inspection_time.as_secs_f64()) < 0.00 {
-                // if inspection goes longer than 15 seconds it is a penalty
-                self.time_color = Color::Red;
-                if 15.00 - self.inspection_time.as_secs_f64() < -2.00 {
-                    // if inspection goes longer than 17 seconds it is a DNF
-                    self.penalty = Penalty::DNF;
-                    self.times.toggle_penalty(Penalty::DNF);
-                    return self.font.convert("DNF").unwrap().to_string();
-                }
-                // if inspection goes longer than 15 seconds but less than 17 seconds it is a +2
-                self.penalty = Penalty::PlusTwo;
-                self.times.toggle_penalty(Penalty::PlusTwo);
-                return self.font.convert("+2").unwrap().to_string();
-            }
-
-            // if inspection is less than 15 seconds, display the time
-            return self
-                .font
-                .convert(&format!(
-                    "{:.0}",
-                    15.00 - self.inspection_time.as_secs_f64()
-                ))
+    pub fn time_string(&self) -> String {
+        if self.inspection_timer.running {
+            let seconds = self.inspection.seconds().unwrap_or(0) as f64;
+            match self.penalty {
+                // if inspection goes more than 2 seconds over it is a DNF
+                Penalty::DNF => self.font.convert("DNF").unwrap().to_string(),
+                // if inspection goes over by up to 2 seconds it is a +2
+                Penalty::PlusTwo => self.font.convert("+2").unwrap().to_string(),
+                // if inspection isn't over, display the time left
+                Penalty::None => self
+                    .font
+                    .convert(&format!(
+                        "{:.0}",
+                        seconds - self.inspection_time.as_secs_f64()
+                    ))
+                    .unwrap()
+                    .to_string(),
+            }
+        } else if self.mode == Mode::Mbld {
+            // attempts run for minutes, shown without the fraction
+            self.font
+                .convert(&mbld::clock(self.time.as_secs_f64()))
                 .unwrap()
-                .to_string();
-        } else if self.state == Idle && self.time != Duration::new(0, 0) {
-            // if idle, display the current time
+                .to_string()
+        } else if self.state != Timing
+            && self.mode == Mode::Timer
+            && self.time != Duration::new(0, 0)
+        {
+            // if not timing, display the current time
             if self.times.currents()[0] == "NA" {
                 return self
                     .font
@@ -173,17 +601,15 @@ impl App {
                     .unwrap()
                     .to_string();
             }
-            return self
-                .font
+            self.font
                 .convert(&format!("{:.5}", self.times.currents()[0]))
                 .unwrap()
-                .to_string();
+                .to_string()
         } else {
-            return self
-                .font
+            self.font
                 .convert(&format!("{:.3}", self.time.as_secs_f64()))
                 .unwrap()
-                .to_string();
+                .to_string()
         }
     }
 
@@ -192,15 +618,283 @@ impl App {
         self.time_color = color;
     }
 
-    /// Refresh scramble with a new one.
+    /// Refresh scramble with a new one, taken from the scramble set while it lasts.
     pub fn new_scramble(&mut self) {
-        self.scramble = Scramble::new(20);
+        self.case = None;
+        if let (Mode::Mbld, Some(mbld)) = (self.mode, self.mbld.as_mut()) {
+            // scramble the attempt's cubes afresh, once there are any
+            let cubes = mbld.cubes();
+            mbld.scramble(cubes, &mut self.rng);
+            return;
+        }
+        let solves = (self.mode == Mode::Trainer && self.trainer.is_some()) || self.drills_subset();
+        self.generating = solves && !solver::ready();
+        if self.generating {
+            // the solver's tables take a moment to build, the scramble is
+            // generated on a tick once they are
+            solver::prepare();
+            return;
+        }
+        if self.mode == Mode::Trainer {
+            if let Some(trainer) = self.trainer.as_mut() {
+                match trainer.next_case(&mut self.rng, &self.times, schedule::now()) {
+                    Some(scramble) => self.scramble = scramble,
+                    None => self.message = Some("No cases selected".to_string()),
+                }
+                return;
+            }
+        }
+        self.scramble = match self.scramble_set.as_mut().and_then(|set| set.take_next()) {
+            Some(scramble) => scramble,
+            None if self.drills_subset() => match self.subset.scramble(&mut self.rng) {
+                Some((scramble, case)) => {
+                    self.case = case;
+                    scramble
+                }
+                None => {
+                    self.message = Some(format!("No {} scramble found in time", self.subset));
+                    Scramble::for_variant(self.variant, &mut self.rng)
+                }
+            },
+            None => Scramble::for_variant(self.variant, &mut self.rng),
+        };
+        if self.mode == Mode::Virtual {
+            self.virtual_cube = Some(VirtualCube::new(self.scrambled_cube()));
+        }
+    }
+
+    /// Switch between timing solves and multi-blind attempts, which start by
+    /// asking for the number of cubes.
+    pub fn toggle_mbld(&mut self) {
+        self.time = Duration::new(0, 0);
+        match self.mode {
+            Mode::Mbld => {
+                self.mode = Mode::Timer;
+                self.mbld = None;
+                self.new_scramble();
+            }
+            _ => {
+                self.mode = Mode::Mbld;
+                self.mbld = Some(Mbld::new());
+            }
+        }
+    }
+
+    /// Go on with the count typed in multi-blind mode: scramble that many
+    /// cubes, or save the attempt with the cubes solved and scramble the next.
+    pub fn enter_mbld(&mut self) {
+        let Some(mbld) = self.mbld.as_mut() else {
+            return;
+        };
+        let Some(input) = mbld.input.clone() else {
+            return;
+        };
+        match mbld.time {
+            None => match mbld::parse_cubes(&input) {
+                Ok(cubes) => {
+                    mbld.scramble(cubes, &mut self.rng);
+                    mbld.input = None;
+                }
+                Err(e) => self.message = Some(e),
+            },
+            Some(time) => match MbldResult::parse(&input, mbld.cubes(), time) {
+                Ok(result) => {
+                    self.times.add_mbld(result, &mbld.scrambles);
+                    mbld.scramble(mbld.cubes(), &mut self.rng);
+                    mbld.input = None;
+                    mbld.time = None;
+                    self.state = Idle;
+                }
+                Err(e) => self.message = Some(e),
+            },
+        }
+    }
+
+    /// Back out of the count being typed in multi-blind mode: leave the mode
+    /// rather than pick the cubes, or throw the attempt away rather than save it.
+    pub fn cancel_mbld(&mut self) {
+        let Some(mbld) = self.mbld.as_mut() else {
+            return;
+        };
+        match mbld.time {
+            None => self.toggle_mbld(),
+            Some(_) => {
+                mbld.scramble(mbld.cubes(), &mut self.rng);
+                mbld.input = None;
+                mbld.time = None;
+                self.time = Duration::new(0, 0);
+                self.state = Idle;
+            }
+        }
+    }
+
+    /// Whether scrambles come from the session's subset rather than the variant.
+    pub fn drills_subset(&self) -> bool {
+        self.scramble_set.is_none()
+            && self.subset != Subset::None
+            && matches!(self.variant, Variant::ThreeByThree | Variant::OneHanded)
+    }
+
+    /// Switch the session to the next training subset.
+    pub fn next_subset(&mut self) {
+        self.subset = self.subset.next();
+        self.times.set_subset(self.subset);
+        self.new_scramble();
+    }
+
+    /// Use a pre-generated scramble set, starting with its first scramble.
+    pub fn load_scramble_set(&mut self, set: ScrambleSet) {
+        self.scramble_set = Some(set);
+        self.new_scramble();
+    }
+
+    /// Train algorithm cases, starting with a case from the set.
+    pub fn load_trainer(&mut self, trainer: Trainer) {
+        self.trainer = Some(trainer);
+        self.mode = Mode::Trainer;
+        self.new_scramble();
     }
 
-    /// Start inspecting.
-    pub fn start_inspection(&mut self) {
+    /// Switch between timing solves and training algorithm cases, loading the
+    /// case file the first time.
+    pub fn toggle_trainer(&mut self) {
+        if self.mode == Mode::Trainer {
+            self.mode = Mode::Timer;
+            self.time = Duration::new(0, 0);
+            self.new_scramble();
+            return;
+        }
+        if self.trainer.is_none() {
+            match Trainer::load(&Trainer::default_path()) {
+                Ok(trainer) => self.trainer = Some(trainer),
+                Err(e) => {
+                    self.message = Some(format!("Couldn't load cases: {}", e));
+                    return;
+                }
+            }
+        }
+        self.mode = Mode::Trainer;
+        self.time = Duration::new(0, 0);
+        self.new_scramble();
+    }
+
+    /// Switch between timing solves and solving the virtual cube, which
+    /// starts out with the current scramble.
+    pub fn toggle_virtual(&mut self) {
+        self.timer.stop();
+        self.timer.reset();
+        self.time = Duration::new(0, 0);
+        self.state = Idle;
+        match self.mode {
+            Mode::Virtual => {
+                self.mode = Mode::Timer;
+                self.virtual_cube = None;
+            }
+            _ => {
+                self.mode = Mode::Virtual;
+                self.virtual_cube = Some(VirtualCube::new(self.scrambled_cube()));
+            }
+        }
+    }
+
+    /// Give up on the virtual cube solve and start over with a new scramble.
+    pub fn restart_virtual(&mut self) {
+        self.timer.stop();
+        self.timer.reset();
+        self.time = Duration::new(0, 0);
+        self.state = Idle;
+        self.new_scramble();
+    }
+
+    /// Turn the virtual cube. The first turn that isn't a rotation starts the
+    /// timer, and the solve is saved with its moves once the cube is solved.
+    pub fn turn(&mut self, m: Move, at: Instant) {
+        let Some(virtual_cube) = self.virtual_cube.as_mut() else {
+            return;
+        };
+        if self.state == Idle && m.layers != Layers::Rotation {
+            self.state = Timing;
+            self.timer.reset();
+            self.timer.start_at(at);
+        }
+        let time = match self.state {
+            Timing => self.timer.time_at(at),
+            _ => Duration::new(0, 0),
+        };
+        virtual_cube.turn(m, time);
+
+        if self.state == Timing && virtual_cube.cube.is_solved() {
+            self.time = self.timer.stop_at(at);
+            self.state = Idle;
+            let reconstruction = virtual_cube.reconstruction();
+            self.times.add_time(
+                self.time.as_secs_f64(),
+                self.scramble.to_string(),
+                Penalty::None,
+            );
+            self.times.set_last("reconstruction", reconstruction);
+            self.new_scramble();
+        }
+    }
+
+    /// Open or close the list of cases to train.
+    pub fn toggle_case_list(&mut self) {
+        if let Some(trainer) = self.trainer.as_mut() {
+            trainer.choosing = !trainer.choosing;
+            // the current case may have been deselected
+            if !trainer.choosing {
+                self.new_scramble();
+            }
+        }
+    }
+
+    /// Record that the last trained case wasn't recognised, grading its
+    /// review again as forgotten. Extra practice leaves the schedule as is.
+    pub fn record_miss(&mut self) {
+        if let Some(trainer) = self.trainer.as_mut() {
+            if let (Some(case), true) = (trainer.last, trainer.missable) {
+                trainer.missable = false;
+                let name = &trainer.cases[case].name;
+                self.times.add_trainer_miss(name);
+                if let Some(previous) = trainer.undo.take() {
+                    self.times
+                        .set_schedule(name, previous.review(schedule::FORGOTTEN, schedule::now()));
+                }
+            }
+        }
+    }
+
+    /// Switch the session to the next event variant.
+    pub fn next_variant(&mut self) {
+        self.variant = self.variant.next();
+        self.times.set_variant(self.variant);
+        self.inspection = self.times.inspection(self.variant);
+        // the table now lists the new event's times
+        self.table_state.select(None);
+        self.new_scramble();
+    }
+
+    /// Switch the session's inspection for the current variant: none, WCA,
+    /// then the custom length from the config.
+    pub fn next_inspection(&mut self) {
+        self.inspection = self.inspection.next(self.config.custom_inspection);
+        self.times.set_inspection(self.variant, self.inspection);
+    }
+
+    /// Toggle the net preview of the scrambled cube.
+    pub fn toggle_net(&mut self) {
+        self.show_net = !self.show_net;
+    }
+
+    /// The cube with the current scramble applied.
+    pub fn scrambled_cube(&self) -> Cube {
+        Cube::scrambled(self.variant.puzzle_size(), &self.scramble.moves)
+    }
+
+    /// Start inspecting from `at`.
+    pub fn start_inspection(&mut self, at: Instant) {
         self.state = Inspecting;
-        self.inspection_timer.start();
+        self.inspection_timer.start_at(at);
     }
 
     /// Stop inspecting.
@@ -215,18 +909,144 @@ impl App {
         self.new_scramble();
     }
 
+    /// Move the selection in the times table, newest solve first.
+    pub fn select_time(&mut self, down: bool) {
+        let rows = self.times.num_times();
+        if rows == 0 {
+            return;
+        }
+        // the details are likely to be opened next
+        hints::prepare();
+        let row = match (self.table_state.selected(), down) {
+            (None, _) => 0,
+            (Some(row), true) => (row + 1).min(rows - 1),
+            (Some(row), false) => row.saturating_sub(1),
+        };
+        self.table_state.select(Some(row));
+    }
+
+    /// Open the detail popup of the selected solve, with optimal first step
+    /// solutions for its scramble.
+    pub fn open_detail(&mut self) {
+        let Some(row) = self.table_state.selected() else {
+            return;
+        };
+        let Some(index) = self.times.num_times().checked_sub(row + 1) else {
+            return;
+        };
+        self.detail = Some(index);
+        self.playback = 0;
+        self.find_hints();
+    }
+
+    /// Searches for the hints of the solve in the detail popup, once their
+    /// tables are built. Only 3x3 solves get hints.
+    fn find_hints(&mut self) {
+        self.hints = None;
+        self.searching = false;
+        if self.detail.is_none() || self.detail_event().puzzle_size() != 3 {
+            return;
+        }
+        let Some(scramble) = self.detail_scramble() else {
+            return;
+        };
+        if !hints::ready() {
+            hints::prepare();
+            self.searching = true;
+            return;
+        }
+        self.hints = Some(hints::hints(&scramble.moves, self.config.hint_budget));
+    }
+
+    /// Set the penalty of the solve in the detail popup.
+    pub fn set_detail_penalty(&mut self, penalty: Penalty) {
+        if let Some(index) = self.detail {
+            self.times.set_penalty(index, penalty);
+        }
+    }
+
+    /// Close the detail popup.
+    pub fn close_detail(&mut self) {
+        self.detail = None;
+        self.hints = None;
+        self.searching = false;
+        self.reconstruction_input = None;
+    }
+
+    /// Event of the solve in the detail popup, the session's if none is open.
+    pub fn detail_event(&self) -> Variant {
+        self.detail
+            .map_or(self.variant, |index| self.times.event(index))
+    }
+
+    /// Scramble of the solve in the detail popup.
+    pub fn detail_scramble(&self) -> Option<Scramble> {
+        let index = self.detail?;
+        self.times.times["times"][index]["scramble"]
+            .as_str()?
+            .parse()
+            .ok()
+    }
+
+    /// Reconstruction of the solve in the detail popup.
+    pub fn detail_reconstruction(&self) -> Option<Reconstruction> {
+        self.times.reconstruction(self.detail?)
+    }
+
+    /// Step the reconstruction shown on the net forward or back one move.
+    pub fn step_playback(&mut self, forward: bool) {
+        let moves = self
+            .detail_reconstruction()
+            .map_or(0, |reconstruction| reconstruction.moves.len());
+        self.playback = match forward {
+            true => (self.playback + 1).min(moves),
+            false => self.playback.saturating_sub(1),
+        };
+    }
+
+    /// Start typing a reconstruction for the solve in the detail popup,
+    /// starting from the one it already has.
+    pub fn edit_reconstruction(&mut self) {
+        let text = self
+            .detail_reconstruction()
+            .map(|reconstruction| reconstruction.to_text())
+            .unwrap_or_default();
+        self.reconstruction_input = Some(text);
+    }
+
+    /// Attach the typed reconstruction to the solve if it solves its scramble,
+    /// otherwise keep editing and show why.
+    pub fn save_reconstruction(&mut self) {
+        let (Some(index), Some(text)) = (self.detail, &self.reconstruction_input) else {
+            return;
+        };
+        let scramble = self.detail_scramble().unwrap_or(Scramble { moves: vec![] });
+        let result = Reconstruction::parse(text).and_then(|reconstruction| {
+            reconstruction.validate(&scramble.moves, self.detail_event().puzzle_size())?;
+            Ok(reconstruction)
+        });
+        match result {
+            Ok(reconstruction) => {
+                self.times.set_reconstruction(index, &reconstruction);
+                self.reconstruction_input = None;
+                self.playback = 0;
+            }
+            Err(e) => self.message = Some(format!("Couldn't save reconstruction: {}", e)),
+        }
+    }
+
     /// Get the last scramble.
     pub fn last_scramble(&self) -> String {
-        if (self.times.times["times"].as_array().unwrap().len()) == 0 {
+        if self.times.times["times"].as_array().unwrap().is_empty() {
             return String::from("No scrambles yet.");
         }
-        return self.times.times["times"]
+        self.times.times["times"]
             .as_array()
             .unwrap()
             .last()
             .unwrap()["scramble"]
             .as_str()
             .unwrap()
-            .to_string();
+            .to_string()
     }
 }
@@ -0,0 +1,160 @@
+use home;
+use serde_json::{json, Value};
+use std::fs::File;
+use std::io::{Read, Write};
+use std::path::PathBuf;
+use std::time::Duration;
+use tui::style::Color;
+
+use crate::input::InputMode;
+use crate::keymap::Keymap;
+use crate::turns::Direction;
+
+/// Sticker colours used to draw the cube.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum ColourScheme {
+    /// White top, green front, yellow opposite white.
+    Wca,
+    /// White top, green front, blue opposite white.
+    Japanese,
+}
+
+impl ColourScheme {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "wca" => Some(ColourScheme::Wca),
+            "japanese" => Some(ColourScheme::Japanese),
+            _ => None,
+        }
+    }
+
+    /// Colour of the stickers that start on `face`.
+    pub fn colour(&self, face: Direction) -> Color {
+        let orange = Color::Rgb(255, 140, 0);
+        match (self, face) {
+            (_, Direction::Up) => Color::White,
+            (_, Direction::Front) => Color::Green,
+            (_, Direction::Right) => Color::Red,
+            (_, Direction::Left) => orange,
+            (ColourScheme::Wca, Direction::Down) => Color::Yellow,
+            (ColourScheme::Wca, Direction::Back) => Color::Blue,
+            (ColourScheme::Japanese, Direction::Down) => Color::Blue,
+            (ColourScheme::Japanese, Direction::Back) => Color::Yellow,
+        }
+    }
+}
+
+/// User settings, read from `config.json` next to the times file.
+#[derive(Debug)]
+pub struct Config {
+    pub colour_scheme: ColourScheme,
+    /// Time allowed to search for all the solver hints of a solve.
+    pub hint_budget: Duration,
+    /// Phases of a solve, each ended by a press of space, e.g. cross, F2L,
+    /// OLL and PLL. A single phase when empty.
+    pub phases: Vec<String>,
+    /// Seconds of custom inspection.
+    pub custom_inspection: u64,
+    /// Whether inspection warnings ring the terminal bell.
+    pub inspection_bell: bool,
+    /// How long space is held before the timer is ready to start.
+    pub hold: Duration,
+    /// How space is read, detected from the terminal when not set.
+    pub input: Option<InputMode>,
+    /// Keys for everything but timing.
+    pub keymap: Keymap,
+    /// Problems with the config file, shown on startup.
+    pub errors: Vec<String>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            colour_scheme: ColourScheme::Wca,
+            hint_budget: Duration::from_millis(500),
+            phases: vec![],
+            custom_inspection: 10,
+            inspection_bell: false,
+            hold: Duration::from_millis(300),
+            input: None,
+            keymap: Keymap::default(),
+            errors: vec![],
+        }
+    }
+}
+
+impl Config {
+    /// Loads the config file, creating it with the defaults if it doesn't exist.
+    /// Missing or unknown settings fall back to their defaults.
+    pub fn load() -> Self {
+        let mut path = PathBuf::new();
+        path.push(home::home_dir().unwrap());
+        path.push(".cargo/bin/cube-times");
+        path.push("config.json");
+
+        if !path.exists() {
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            let mut file = File::create(&path).unwrap();
+            let json = json!({
+                "colour_scheme": "wca",
+                "hint_budget_ms": 500,
+                "phases": [],
+                "custom_inspection": 10,
+                "inspection_bell": false,
+                "hold_ms": 300,
+                "input": "auto",
+                "keys": Keymap::default().to_value(),
+            });
+            file.write_all(serde_json::to_string_pretty(&json).unwrap().as_bytes())
+                .unwrap();
+        }
+
+        let mut contents = String::new();
+        if let Ok(mut file) = File::open(&path) {
+            file.read_to_string(&mut contents).unwrap_or_default();
+        }
+        Self::from_value(&serde_json::from_str(&contents).unwrap_or(Value::Null))
+    }
+
+    /// Reads settings from parsed config json.
+    pub fn from_value(json: &Value) -> Self {
+        let defaults = Self::default();
+        let mut errors = vec![];
+        let keymap = Keymap::from_value(&json["keys"]).unwrap_or_else(|e| {
+            errors.push(format!("Key bindings: {}, using the defaults", e));
+            defaults.keymap.clone()
+        });
+        Self {
+            colour_scheme: json["colour_scheme"]
+                .as_str()
+                .and_then(ColourScheme::from_name)
+                .unwrap_or(defaults.colour_scheme),
+            hint_budget: json["hint_budget_ms"]
+                .as_u64()
+                .map(Duration::from_millis)
+                .unwrap_or(defaults.hint_budget),
+            phases: json["phases"]
+                .as_array()
+                .map(|phases| {
+                    phases
+                        .iter()
+                        .filter_map(|phase| phase.as_str().map(String::from))
+                        .collect()
+                })
+                .unwrap_or(defaults.phases),
+            custom_inspection: json["custom_inspection"]
+                .as_u64()
+                .unwrap_or(defaults.custom_inspection),
+            inspection_bell: json["inspection_bell"]
+                .as_bool()
+                .unwrap_or(defaults.inspection_bell),
+            hold: json["hold_ms"]
+                .as_u64()
+                .map(Duration::from_millis)
+                .unwrap_or(defaults.hold),
+            input: json["input"].as_str().and_then(InputMode::from_name),
+            keymap,
+            errors,
+        }
+    }
+}
@@ -0,0 +1,169 @@
+use crate::turns::{Direction, Layers, Move};
+
+/// Faces in net order: the U face sits above F, D below it, and L, F, R, B
+/// run left to right through the middle.
+pub const FACES: [Direction; 6] = [
+    Direction::Up,
+    Direction::Left,
+    Direction::Front,
+    Direction::Right,
+    Direction::Back,
+    Direction::Down,
+];
+
+/// A single sticker of the cube.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Sticker {
+    /// Centre of the piece the sticker is on. Coordinates are doubled so they
+    /// stay integers on even cubes: they run from -(n-1) to n-1 in steps of 2,
+    /// with x towards R, y towards U and z towards F.
+    pos: [i32; 3],
+    /// Outward normal of the side the sticker is on.
+    normal: [i32; 3],
+    /// Face the sticker started on, i.e. its colour.
+    colour: Direction,
+}
+
+/// Sticker model of an NxN cube, in WCA scrambling orientation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cube {
+    pub size: u8,
+    stickers: Vec<Sticker>,
+}
+
+/// Axis index and sign of the outward normal of a face.
+fn axis(face: Direction) -> (usize, i32) {
+    match face {
+        Direction::Right => (0, 1),
+        Direction::Left => (0, -1),
+        Direction::Up => (1, 1),
+        Direction::Down => (1, -1),
+        Direction::Front => (2, 1),
+        Direction::Back => (2, -1),
+    }
+}
+
+/// Screen directions (right, down) of a face when drawn in the net.
+fn net_orientation(face: Direction) -> ([i32; 3], [i32; 3]) {
+    match face {
+        Direction::Up => ([1, 0, 0], [0, 0, 1]),
+        Direction::Front => ([1, 0, 0], [0, -1, 0]),
+        Direction::Right => ([0, 0, -1], [0, -1, 0]),
+        Direction::Back => ([-1, 0, 0], [0, -1, 0]),
+        Direction::Left => ([0, 0, 1], [0, -1, 0]),
+        Direction::Down => ([1, 0, 0], [0, 0, -1]),
+    }
+}
+
+fn dot(a: [i32; 3], b: [i32; 3]) -> i32 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+/// Rotates a vector a quarter turn clockwise, as seen looking down the
+/// positive end of the axis.
+fn rotate_clockwise(v: [i32; 3], axis: usize) -> [i32; 3] {
+    let [x, y, z] = v;
+    match axis {
+        0 => [x, z, -y],
+        1 => [-z, y, x],
+        _ => [y, -x, z],
+    }
+}
+
+impl Cube {
+    /// A solved cube with `size` layers.
+    pub fn new(size: u8) -> Self {
+        let n = size as i32;
+        let mut stickers = vec![];
+        for face in FACES {
+            let (right, down) = net_orientation(face);
+            let (axis, sign) = axis(face);
+            let mut normal = [0; 3];
+            normal[axis] = sign;
+            for row in 0..n {
+                for col in 0..n {
+                    let mut pos = [0; 3];
+                    for i in 0..3 {
+                        pos[i] = normal[i] * (n - 1)
+                            + right[i] * (2 * col - (n - 1))
+                            + down[i] * (2 * row - (n - 1));
+                    }
+                    stickers.push(Sticker {
+                        pos,
+                        normal,
+                        colour: face,
+                    });
+                }
+            }
+        }
+        Cube { size, stickers }
+    }
+
+    /// A cube of `size` layers with `moves` applied.
+    pub fn scrambled(size: u8, moves: &[Move]) -> Self {
+        let mut cube = Self::new(size);
+        cube.apply_all(moves);
+        cube
+    }
+
+    /// Applies a single move.
+    pub fn apply(&mut self, m: &Move) {
+        let n = self.size as i32;
+        let (axis, sign) = axis(m.direction);
+        // a clockwise turn of a negative face is anticlockwise about the axis
+        let quarter_turns = match sign {
+            1 => m.turn.quarter_turns(),
+            _ => 4 - m.turn.quarter_turns(),
+        };
+
+        for sticker in self.stickers.iter_mut() {
+            // distance from the turned face, 0 for the outer layer
+            let depth = (n - 1 - sticker.pos[axis] * sign) / 2;
+            let turned = match m.layers {
+                Layers::Outer => depth == 0,
+                Layers::Wide(layers) => depth < layers as i32,
+                Layers::Slice => depth > 0 && depth < n - 1,
+                Layers::Rotation => true,
+            };
+            if turned {
+                for _ in 0..quarter_turns {
+                    sticker.pos = rotate_clockwise(sticker.pos, axis);
+                    sticker.normal = rotate_clockwise(sticker.normal, axis);
+                }
+            }
+        }
+    }
+
+    /// Applies a sequence of moves.
+    pub fn apply_all(&mut self, moves: &[Move]) {
+        for m in moves {
+            self.apply(m);
+        }
+    }
+
+    /// Colours of a face as rows from top to bottom, in the orientation it is
+    /// drawn in the net.
+    pub fn face(&self, face: Direction) -> Vec<Vec<Direction>> {
+        let n = self.size as i32;
+        let (axis, sign) = axis(face);
+        let (right, down) = net_orientation(face);
+        let mut grid = vec![vec![face; self.size as usize]; self.size as usize];
+        for sticker in &self.stickers {
+            if sticker.normal[axis] == sign {
+                let col = (dot(sticker.pos, right) + n - 1) / 2;
+                let row = (dot(sticker.pos, down) + n - 1) / 2;
+                grid[row as usize][col as usize] = sticker.colour;
+            }
+        }
+        grid
+    }
+
+    /// Whether every face shows a single colour, in any orientation.
+    pub fn is_solved(&self) -> bool {
+        FACES.iter().all(|&face| {
+            let grid = self.face(face);
+            let colour = grid[0][0];
+            grid.iter().flatten().all(|&c| c == colour)
+        })
+    }
+}
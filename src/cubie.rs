@@ -0,0 +1,360 @@
+use crate::cube::Cube;
+use crate::turns::{Direction, Layers, Move, Turn};
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+// Corner positions.
+pub const URF: usize = 0;
+pub const UFL: usize = 1;
+pub const ULB: usize = 2;
+pub const UBR: usize = 3;
+pub const DFR: usize = 4;
+pub const DLF: usize = 5;
+pub const DBL: usize = 6;
+pub const DRB: usize = 7;
+
+// Edge positions.
+pub const UR: usize = 0;
+pub const UF: usize = 1;
+pub const UL: usize = 2;
+pub const UB: usize = 3;
+pub const DR: usize = 4;
+pub const DF: usize = 5;
+pub const DL: usize = 6;
+pub const DB: usize = 7;
+pub const FR: usize = 8;
+pub const FL: usize = 9;
+pub const BL: usize = 10;
+pub const BR: usize = 11;
+
+/// Piece level model of a 3x3 cube, with fixed centres.
+///
+/// `cp[i]` is the corner piece in position `i` and `co[i]` its twist (0-2),
+/// `ep[i]` and `eo[i]` the same for edges. Orientation follows Kociemba's
+/// convention, so edges are flipped by F and B quarter turns.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CubieCube {
+    pub cp: [u8; 8],
+    pub co: [u8; 8],
+    pub ep: [u8; 12],
+    pub eo: [u8; 12],
+}
+
+/// Clockwise quarter turns of U, R, F, D, L and B.
+const BASIC_MOVES: [CubieCube; 6] = [
+    CubieCube {
+        cp: [3, 0, 1, 2, 4, 5, 6, 7],
+        co: [0; 8],
+        ep: [3, 0, 1, 2, 4, 5, 6, 7, 8, 9, 10, 11],
+        eo: [0; 12],
+    },
+    CubieCube {
+        cp: [4, 1, 2, 0, 7, 5, 6, 3],
+        co: [2, 0, 0, 1, 1, 0, 0, 2],
+        ep: [8, 1, 2, 3, 11, 5, 6, 7, 4, 9, 10, 0],
+        eo: [0; 12],
+    },
+    CubieCube {
+        cp: [1, 5, 2, 3, 0, 4, 6, 7],
+        co: [1, 2, 0, 0, 2, 1, 0, 0],
+        ep: [0, 9, 2, 3, 4, 8, 6, 7, 1, 5, 10, 11],
+        eo: [0, 1, 0, 0, 0, 1, 0, 0, 1, 1, 0, 0],
+    },
+    CubieCube {
+        cp: [0, 1, 2, 3, 5, 6, 7, 4],
+        co: [0; 8],
+        ep: [0, 1, 2, 3, 5, 6, 7, 4, 8, 9, 10, 11],
+        eo: [0; 12],
+    },
+    CubieCube {
+        cp: [0, 2, 6, 3, 4, 1, 5, 7],
+        co: [0, 1, 2, 0, 0, 2, 1, 0],
+        ep: [0, 1, 10, 3, 4, 5, 9, 7, 8, 2, 6, 11],
+        eo: [0; 12],
+    },
+    CubieCube {
+        cp: [0, 1, 3, 7, 4, 5, 2, 6],
+        co: [0, 0, 1, 2, 0, 0, 2, 1],
+        ep: [0, 1, 2, 11, 4, 5, 6, 10, 8, 9, 3, 7],
+        eo: [0, 0, 0, 1, 0, 0, 0, 1, 0, 0, 1, 1],
+    },
+];
+
+/// Faces in the order of [`BASIC_MOVES`].
+pub const FACE_ORDER: [Direction; 6] = [
+    Direction::Up,
+    Direction::Right,
+    Direction::Front,
+    Direction::Down,
+    Direction::Left,
+    Direction::Back,
+];
+
+/// Index of the basic move turning `face`.
+pub fn face_index(face: Direction) -> usize {
+    FACE_ORDER.iter().position(|&f| f == face).unwrap()
+}
+
+/// The 18 face turns, indexed `3 * face + quarter turns - 1`.
+pub fn move_cube(index: usize) -> CubieCube {
+    let mut cube = CubieCube::SOLVED;
+    for _ in 0..index % 3 + 1 {
+        cube = cube.multiply(&BASIC_MOVES[index / 3]);
+    }
+    cube
+}
+
+/// Face turn for a move index, the inverse of [`move_index`].
+pub fn index_move(index: usize) -> Move {
+    let turn = Turn::from_quarter_turns(index as u8 % 3 + 1).unwrap();
+    Move::new(FACE_ORDER[index / 3], turn)
+}
+
+/// Move index of a face turn, `None` for wide moves, slices and rotations.
+pub fn move_index(m: &Move) -> Option<usize> {
+    match m.layers {
+        Layers::Outer => Some(3 * face_index(m.direction) + m.turn.quarter_turns() as usize - 1),
+        _ => None,
+    }
+}
+
+/// Number of inversions of a permutation, mod 2.
+fn parity(perm: &[u8]) -> bool {
+    let mut odd = false;
+    for i in 0..perm.len() {
+        for j in i + 1..perm.len() {
+            if perm[i] > perm[j] {
+                odd = !odd;
+            }
+        }
+    }
+    odd
+}
+
+impl CubieCube {
+    pub const SOLVED: CubieCube = CubieCube {
+        cp: [0, 1, 2, 3, 4, 5, 6, 7],
+        co: [0; 8],
+        ep: [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11],
+        eo: [0; 12],
+    };
+
+    /// The state after applying `other` to `self`.
+    pub fn multiply(&self, other: &CubieCube) -> CubieCube {
+        let mut result = CubieCube::SOLVED;
+        for i in 0..8 {
+            let from = other.cp[i] as usize;
+            result.cp[i] = self.cp[from];
+            result.co[i] = (self.co[from] + other.co[i]) % 3;
+        }
+        for i in 0..12 {
+            let from = other.ep[i] as usize;
+            result.ep[i] = self.ep[from];
+            result.eo[i] = (self.eo[from] + other.eo[i]) % 2;
+        }
+        result
+    }
+
+    /// Applies the face turn with the given move index.
+    pub fn apply_index(&mut self, index: usize) {
+        *self = self.multiply(&move_cube(index));
+    }
+
+    /// The state reached by applying `moves` to a solved cube, `None` if a
+    /// move is not a face turn.
+    pub fn from_moves(moves: &[Move]) -> Option<Self> {
+        let mut cube = CubieCube::SOLVED;
+        for m in moves {
+            cube.apply_index(move_index(m)?);
+        }
+        Some(cube)
+    }
+
+    /// Whether the state can be reached by turning faces.
+    pub fn is_solvable(&self) -> bool {
+        let corner_twist: u8 = self.co.iter().sum();
+        let edge_flip: u8 = self.eo.iter().sum();
+        corner_twist % 3 == 0 && edge_flip % 2 == 0 && parity(&self.cp) == parity(&self.ep)
+    }
+
+    /// Random state in which only the given corner and edge positions are
+    /// shuffled among themselves. Shuffled pieces are twisted or flipped at
+    /// random when `twist` or `flip` is set, everything else stays solved.
+    pub fn random_with<R: Rng + ?Sized>(
+        rng: &mut R,
+        corners: &[usize],
+        twist: bool,
+        edges: &[usize],
+        flip: bool,
+    ) -> Self {
+        let mut cube = CubieCube::SOLVED;
+
+        let mut pieces: Vec<u8> = corners.iter().map(|&c| c as u8).collect();
+        pieces.shuffle(rng);
+        for (&pos, &piece) in corners.iter().zip(&pieces) {
+            cube.cp[pos] = piece;
+        }
+        let mut pieces: Vec<u8> = edges.iter().map(|&e| e as u8).collect();
+        pieces.shuffle(rng);
+        for (&pos, &piece) in edges.iter().zip(&pieces) {
+            cube.ep[pos] = piece;
+        }
+
+        // corner and edge permutations must have the same parity
+        if parity(&cube.cp) != parity(&cube.ep) {
+            if edges.len() >= 2 {
+                cube.ep.swap(edges[0], edges[1]);
+            } else if corners.len() >= 2 {
+                cube.cp.swap(corners[0], corners[1]);
+            }
+        }
+
+        if twist {
+            for &pos in corners {
+                cube.co[pos] = rng.gen_range(0..3);
+            }
+            let last = *corners.last().unwrap();
+            let rest: u8 = cube.co.iter().sum::<u8>() - cube.co[last];
+            cube.co[last] = (3 - rest % 3) % 3;
+        }
+        if flip {
+            for &pos in edges {
+                cube.eo[pos] = rng.gen_range(0..2);
+            }
+            let last = *edges.last().unwrap();
+            let rest: u8 = cube.eo.iter().sum::<u8>() - cube.eo[last];
+            cube.eo[last] = rest % 2;
+        }
+
+        cube
+    }
+}
+
+/// Inverse of a move sequence.
+pub fn invert(moves: &[Move]) -> Vec<Move> {
+    moves
+        .iter()
+        .rev()
+        .map(|m| Move {
+            turn: m.turn.opposite(),
+            ..*m
+        })
+        .collect()
+}
+
+/// Stickers of each corner position as (face, row, column) in the net, the
+/// U or D sticker first, then clockwise.
+const CORNER_FACELETS: [[(Direction, usize, usize); 3]; 8] = [
+    [
+        (Direction::Up, 2, 2),
+        (Direction::Right, 0, 0),
+        (Direction::Front, 0, 2),
+    ],
+    [
+        (Direction::Up, 2, 0),
+        (Direction::Front, 0, 0),
+        (Direction::Left, 0, 2),
+    ],
+    [
+        (Direction::Up, 0, 0),
+        (Direction::Left, 0, 0),
+        (Direction::Back, 0, 2),
+    ],
+    [
+        (Direction::Up, 0, 2),
+        (Direction::Back, 0, 0),
+        (Direction::Right, 0, 2),
+    ],
+    [
+        (Direction::Down, 0, 2),
+        (Direction::Front, 2, 2),
+        (Direction::Right, 2, 0),
+    ],
+    [
+        (Direction::Down, 0, 0),
+        (Direction::Left, 2, 2),
+        (Direction::Front, 2, 0),
+    ],
+    [
+        (Direction::Down, 2, 0),
+        (Direction::Back, 2, 2),
+        (Direction::Left, 2, 0),
+    ],
+    [
+        (Direction::Down, 2, 2),
+        (Direction::Right, 2, 2),
+        (Direction::Back, 2, 0),
+    ],
+];
+
+/// Stickers of each edge position, the sticker that decides orientation first.
+const EDGE_FACELETS: [[(Direction, usize, usize); 2]; 12] = [
+    [(Direction::Up, 1, 2), (Direction::Right, 0, 1)],
+    [(Direction::Up, 2, 1), (Direction::Front, 0, 1)],
+    [(Direction::Up, 1, 0), (Direction::Left, 0, 1)],
+    [(Direction::Up, 0, 1), (Direction::Back, 0, 1)],
+    [(Direction::Down, 1, 2), (Direction::Right, 2, 1)],
+    [(Direction::Down, 0, 1), (Direction::Front, 2, 1)],
+    [(Direction::Down, 1, 0), (Direction::Left, 2, 1)],
+    [(Direction::Down, 2, 1), (Direction::Back, 2, 1)],
+    [(Direction::Front, 1, 2), (Direction::Right, 1, 0)],
+    [(Direction::Front, 1, 0), (Direction::Left, 1, 2)],
+    [(Direction::Back, 1, 2), (Direction::Left, 1, 0)],
+    [(Direction::Back, 1, 0), (Direction::Right, 1, 2)],
+];
+
+impl CubieCube {
+    /// Reads the piece state of a 3x3 sticker model, relative to its centres,
+    /// so slices and rotations are taken into account. `None` if the cube is
+    /// not a 3x3 or its stickers don't form valid pieces.
+    pub fn from_cube(cube: &Cube) -> Option<Self> {
+        if cube.size != 3 {
+            return None;
+        }
+        let faces: Vec<Vec<Vec<Direction>>> = FACE_ORDER.iter().map(|&f| cube.face(f)).collect();
+        // the face whose centre has the sticker's colour
+        let sticker = |(face, row, col): (Direction, usize, usize)| {
+            let colour = faces[face_index(face)][row][col];
+            FACE_ORDER
+                .iter()
+                .copied()
+                .find(|&f| faces[face_index(f)][1][1] == colour)
+        };
+
+        let mut state = CubieCube::SOLVED;
+        for (pos, facelets) in CORNER_FACELETS.iter().enumerate() {
+            let colours: Vec<Direction> = facelets
+                .iter()
+                .map(|&f| sticker(f))
+                .collect::<Option<_>>()?;
+            let twist = colours
+                .iter()
+                .position(|&c| c == Direction::Up || c == Direction::Down)?;
+            let piece = CORNER_FACELETS
+                .iter()
+                .position(|home| (0..3).all(|i| home[i].0 == colours[(twist + i) % 3]))?;
+            state.cp[pos] = piece as u8;
+            state.co[pos] = twist as u8;
+        }
+        for (pos, facelets) in EDGE_FACELETS.iter().enumerate() {
+            let colours = [sticker(facelets[0])?, sticker(facelets[1])?];
+            let (piece, flip) =
+                EDGE_FACELETS.iter().enumerate().find_map(|(piece, home)| {
+                    match (home[0].0, home[1].0) {
+                        (a, b) if [a, b] == colours => Some((piece, 0)),
+                        (a, b) if [b, a] == colours => Some((piece, 1)),
+                        _ => None,
+                    }
+                })?;
+            state.ep[pos] = piece as u8;
+            state.eo[pos] = flip;
+        }
+
+        let mut corners = state.cp;
+        corners.sort();
+        let mut edges = state.ep;
+        edges.sort();
+        let valid = corners == CubieCube::SOLVED.cp && edges == CubieCube::SOLVED.ep;
+        (valid && state.is_solvable()).then_some(state)
+    }
+}
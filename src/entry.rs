@@ -0,0 +1,62 @@
+use crate::app::Penalty;
+
+/// Reads a time typed in by hand, e.g. from a stackmat, as seconds and a
+/// penalty. Digits alone are read from the right as hundredths, seconds and
+/// minutes, so `1234` is 12.34 and `10245` is 1:02.45; otherwise it is `12.34`
+/// or `1:02.45`. `12.34+` is 12.34 with a +2 still to add, and `DNF(12.34)`
+/// a DNF.
+pub fn parse_time(text: &str) -> Result<(f64, Penalty), String> {
+    let text = text.trim();
+    let dnf = text
+        .get(..4)
+        .filter(|start| start.eq_ignore_ascii_case("DNF("))
+        .and_then(|_| text[4..].strip_suffix(')'));
+    let (time, penalty) = match (dnf, text.strip_suffix('+')) {
+        (Some(time), _) => (time, Penalty::DNF),
+        (None, Some(time)) => (time, Penalty::PlusTwo),
+        (None, None) => (text, Penalty::None),
+    };
+    let seconds = parse_seconds(time.trim()).ok_or(format!(
+        "\"{}\" isn't a time, e.g. 1234, 12.34 or 1:02.45",
+        text
+    ))?;
+    match seconds > 0.0 {
+        true => Ok((seconds, penalty)),
+        false => Err("A time has to be more than 0".to_string()),
+    }
+}
+
+fn parse_seconds(text: &str) -> Option<f64> {
+    if let Some((minutes, seconds)) = text.split_once(':') {
+        let minutes = digits(minutes)?;
+        let seconds = decimal(seconds).filter(|&seconds| seconds < 60.0)?;
+        return Some(minutes as f64 * 60.0 + seconds);
+    }
+    if text.contains('.') {
+        return decimal(text);
+    }
+
+    // the last two digits are hundredths, the two before them seconds
+    let hundredths = digits(text)?;
+    let (minutes, seconds) = (hundredths / 10_000, hundredths / 100 % 100);
+    if minutes > 0 && seconds >= 60 {
+        return None;
+    }
+    Some((minutes * 6_000 + hundredths % 10_000) as f64 / 100.0)
+}
+
+fn digits(text: &str) -> Option<u64> {
+    match !text.is_empty() && text.bytes().all(|b| b.is_ascii_digit()) {
+        true => text.parse().ok(),
+        false => None,
+    }
+}
+
+fn decimal(text: &str) -> Option<f64> {
+    match text.bytes().any(|b| b.is_ascii_digit())
+        && text.bytes().all(|b| b.is_ascii_digit() || b == b'.')
+    {
+        true => text.parse().ok(),
+        false => None,
+    }
+}
@@ -0,0 +1,127 @@
+use crate::clock::Clock;
+use crate::event::Event;
+use crossterm::event::{KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use std::ffi::c_ulong;
+use std::io::{self, Read};
+use std::mem;
+use std::sync::mpsc;
+
+/// Size of the `timeval` an `input_event` starts with, two longs.
+const TIME_SIZE: usize = 2 * mem::size_of::<c_ulong>();
+/// Size of a `struct input_event`: a `timeval`, then the type, code and value,
+/// 24 bytes on 64-bit Linux and 16 on 32-bit.
+pub const EVENT_SIZE: usize = TIME_SIZE + 8;
+/// Event type of key presses and releases.
+pub const EV_KEY: u16 = 1;
+/// Key code of the space bar.
+pub const KEY_SPACE: u16 = 57;
+
+/// Reads key events from an input device, or anything giving the same bytes.
+#[derive(Debug)]
+pub struct Keyboard<R: Read> {
+    reader: R,
+}
+
+impl<R: Read> Keyboard<R> {
+    pub fn new(reader: R) -> Self {
+        Self { reader }
+    }
+
+    /// The next key event read, skipping other events and keys there is no
+    /// use for, or `None` once the device is gone.
+    pub fn next_key(&mut self) -> io::Result<Option<KeyEvent>> {
+        let mut buf = [0; EVENT_SIZE];
+        loop {
+            match self.reader.read_exact(&mut buf) {
+                Ok(()) => {}
+                Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => return Ok(None),
+                Err(e) => return Err(e),
+            }
+            if let Some(key) = parse(&buf) {
+                return Ok(Some(key));
+            }
+        }
+    }
+}
+
+/// Reads a raw `input_event`, giving a key event for the keys the timer uses.
+pub fn parse(buf: &[u8; EVENT_SIZE]) -> Option<KeyEvent> {
+    let field = &buf[TIME_SIZE..];
+    let kind = u16::from_ne_bytes([field[0], field[1]]);
+    let code = u16::from_ne_bytes([field[2], field[3]]);
+    let value = i32::from_ne_bytes([field[4], field[5], field[6], field[7]]);
+    if kind != EV_KEY {
+        return None;
+    }
+    let code = match code {
+        KEY_SPACE => KeyCode::Char(' '),
+        _ => return None,
+    };
+    let kind = match value {
+        0 => KeyEventKind::Release,
+        1 => KeyEventKind::Press,
+        2 => KeyEventKind::Repeat,
+        _ => return None,
+    };
+    Some(KeyEvent::new_with_kind(code, KeyModifiers::NONE, kind))
+}
+
+/// Writes a raw `input_event` with no timestamp, e.g. for a fake device.
+pub fn encode(kind: u16, code: u16, value: i32) -> [u8; EVENT_SIZE] {
+    let mut buf = [0; EVENT_SIZE];
+    buf[TIME_SIZE..TIME_SIZE + 2].copy_from_slice(&kind.to_ne_bytes());
+    buf[TIME_SIZE + 2..TIME_SIZE + 4].copy_from_slice(&code.to_ne_bytes());
+    buf[TIME_SIZE + 4..].copy_from_slice(&value.to_ne_bytes());
+    buf
+}
+
+/// Sends the keys read from `reader` as device events, stamped by `clock` with
+/// when they were read, until it runs out, the receiver is gone or reading fails.
+pub fn forward<R: Read>(
+    reader: R,
+    sender: &mpsc::Sender<Event>,
+    clock: &dyn Clock,
+) -> io::Result<()> {
+    let mut keyboard = Keyboard::new(reader);
+    while let Some(key) = keyboard.next_key()? {
+        if sender.send(Event::Device(key, clock.now())).is_err() {
+            break;
+        }
+    }
+    Ok(())
+}
+
+/// Opens the keyboards under `/dev/input/by-path` that can be read, failing if
+/// there are none or none can be read, usually for not being in the `input`
+/// group.
+#[cfg(all(feature = "evdev", target_os = "linux"))]
+pub fn open_keyboards() -> io::Result<Vec<std::fs::File>> {
+    let mut keyboards = vec![];
+    let mut error = None;
+    for entry in std::fs::read_dir("/dev/input/by-path")? {
+        let path = entry?.path();
+        if path.to_string_lossy().ends_with("-event-kbd") {
+            match std::fs::File::open(path) {
+                Ok(keyboard) => keyboards.push(keyboard),
+                Err(e) => error = Some(e),
+            }
+        }
+    }
+    match (keyboards.is_empty(), error) {
+        (false, _) => Ok(keyboards),
+        (true, Some(e)) => Err(e),
+        (true, None) => Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            "no keyboards in /dev/input",
+        )),
+    }
+}
+
+/// Without the `evdev` feature there are no keyboards to open.
+#[cfg(not(all(feature = "evdev", target_os = "linux")))]
+pub fn open_keyboards() -> io::Result<Vec<std::fs::File>> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "built without the evdev feature",
+    ))
+}
@@ -1,20 +1,34 @@
 use crate::app::AppResult;
+use crate::clock::{Clock, SystemClock};
+use crate::evdev;
 use crossterm::event::{self, Event as CrosstermEvent, KeyEvent, MouseEvent};
-use std::sync::mpsc;
+use std::io::Read;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc};
 use std::thread;
 use std::time::{Duration, Instant};
 
 /// Terminal events.
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Debug)]
 pub enum Event {
     /// Terminal tick.
     Tick,
-    /// Key press.
-    Key(KeyEvent),
+    /// Key press, with when it was read.
+    Key(KeyEvent, Instant),
     /// Mouse click/scroll.
     Mouse(MouseEvent),
     /// Terminal resize.
     Resize(u16, u16),
+    /// Terminal window focused.
+    FocusGained,
+    /// Terminal window unfocused, after which keys may not reach it.
+    FocusLost,
+    /// Text pasted into the terminal.
+    Paste(String),
+    /// Key read straight from an input device, with when it was read.
+    Device(KeyEvent, Instant),
+    /// The terminal can't be read any more, and why. No events follow.
+    Error(String),
 }
 
 /// Terminal event handler.
@@ -25,36 +39,71 @@ pub struct EventHandler {
     sender: mpsc::Sender<Event>,
     /// Event receiver channel.
     receiver: mpsc::Receiver<Event>,
-    /// Event handler thread.
-    handler: thread::JoinHandle<()>,
+    /// Event handler thread, until it is stopped.
+    handler: Option<thread::JoinHandle<()>>,
+    /// Tells the event handler thread to stop.
+    stop: Arc<AtomicBool>,
+    /// Clock key events are stamped with.
+    clock: Arc<dyn Clock>,
 }
 
 impl EventHandler {
     /// Constructs a new instance of [`EventHandler`].
     pub fn new(tick_rate: u64) -> Self {
+        Self::with_clock(tick_rate, Arc::new(SystemClock))
+    }
+
+    /// Constructs a new instance of [`EventHandler`] stamping key events with
+    /// `clock`, the same one the app is timed by. Ticks still follow real time.
+    pub fn with_clock(tick_rate: u64, clock: Arc<dyn Clock>) -> Self {
         let tick_rate = Duration::from_millis(tick_rate);
         let (sender, receiver) = mpsc::channel();
+        let stop = Arc::new(AtomicBool::new(false));
         let handler = {
             let sender = sender.clone();
+            let clock = clock.clone();
+            let stop = stop.clone();
             thread::spawn(move || {
                 let mut last_tick = Instant::now();
-                loop {
+                // runs until stopped, the terminal can't be read or the app is gone
+                while !stop.load(Ordering::Relaxed) {
                     let timeout = tick_rate
                         .checked_sub(last_tick.elapsed())
                         .unwrap_or(tick_rate);
 
-                    if event::poll(timeout).expect("no events available") {
-                        match event::read().expect("unable to read event") {
-                            CrosstermEvent::Key(e) => sender.send(Event::Key(e)),
-                            CrosstermEvent::Mouse(e) => sender.send(Event::Mouse(e)),
-                            CrosstermEvent::Resize(w, h) => sender.send(Event::Resize(w, h)),
-                            _ => unimplemented!(),
+                    // the receiver waits on the sources too, so it is told
+                    // rather than left waiting when the terminal can't be read
+                    let event = event::poll(timeout).and_then(|ready| match ready {
+                        true => event::read().map(Some),
+                        false => Ok(None),
+                    });
+                    match event {
+                        Ok(Some(event)) => {
+                            // keys are timed from when they are read, not handled
+                            let at = clock.now();
+                            let event = match event {
+                                CrosstermEvent::Key(e) => Event::Key(e, at),
+                                CrosstermEvent::Mouse(e) => Event::Mouse(e),
+                                CrosstermEvent::Resize(w, h) => Event::Resize(w, h),
+                                CrosstermEvent::FocusGained => Event::FocusGained,
+                                CrosstermEvent::FocusLost => Event::FocusLost,
+                                CrosstermEvent::Paste(text) => Event::Paste(text),
+                            };
+                            if sender.send(event).is_err() {
+                                break;
+                            }
+                        }
+                        Ok(None) => {}
+                        Err(e) => {
+                            let _ = sender.send(Event::Error(e.to_string()));
+                            break;
                         }
-                        .expect("failed to send terminal event")
                     }
 
                     if last_tick.elapsed() >= tick_rate {
-                        sender.send(Event::Tick).expect("failed to send tick event");
+                        if sender.send(Event::Tick).is_err() {
+                            break;
+                        }
                         last_tick = Instant::now();
                     }
                 }
@@ -63,7 +112,29 @@ impl EventHandler {
         Self {
             sender,
             receiver,
-            handler,
+            handler: Some(handler),
+            stop,
+            clock,
+        }
+    }
+
+    /// Reads keys from `reader`, e.g. a keyboard under `/dev/input`, on a
+    /// thread of its own, sending them alongside the terminal events.
+    pub fn add_source<R: Read + Send + 'static>(&self, reader: R) {
+        let sender = self.sender.clone();
+        let clock = self.clock.clone();
+        thread::spawn(move || {
+            // a keyboard that goes away just stops sending keys
+            let _ = evdev::forward(reader, &sender, clock.as_ref());
+        });
+    }
+
+    /// Stops the event handler thread, waiting for it to finish, which takes
+    /// up to a tick.
+    pub fn stop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        if let Some(handler) = self.handler.take() {
+            let _ = handler.join();
         }
     }
 
@@ -75,3 +146,9 @@ impl EventHandler {
         Ok(self.receiver.recv()?)
     }
 }
+
+impl Drop for EventHandler {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
@@ -1,77 +1,304 @@
-use crate::app::{App, AppResult, Penalty};
-use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
-use tui::style::Color;
+use crate::app::{App, AppResult, Mode, Penalty, State};
+use crate::event::Event;
+use crate::input::InputMode;
+use crate::keymap::Action;
+use crate::virtual_cube::key_move;
+use crossterm::event::{
+    KeyCode, KeyEvent, KeyEventKind, KeyModifiers, MouseButton, MouseEvent, MouseEventKind,
+};
+use std::time::Instant;
+use tui::layout::Rect;
+
+/// Handles an event from the [`EventHandler`], or replayed from a script.
+///
+/// [`EventHandler`]: crate::event::EventHandler
+pub fn handle_event(event: Event, app: &mut App) -> AppResult<()> {
+    match event {
+        Event::Tick => app.tick(),
+        Event::Key(key_event, at) => handle_key_events(key_event, at, app)?,
+        Event::Device(key_event, at) => handle_device_events(key_event, at, app)?,
+        Event::FocusLost => app.focus_lost(),
+        Event::FocusGained => app.focus_gained(),
+        Event::Paste(text) => app.paste(&text),
+        Event::Mouse(mouse_event) => handle_mouse_events(mouse_event, app)?,
+        // the terminal picks up its new size when it next draws
+        Event::Resize(_, _) => {}
+        Event::Error(error) => return Err(format!("Can't read the terminal: {}", error).into()),
+    }
+    Ok(())
+}
+
+/// Handles the key events and updates the state of [`App`]. `at` is when the
+/// key was read, which times start and stop from.
+pub fn handle_key_events(key_event: KeyEvent, at: Instant, app: &mut App) -> AppResult<()> {
+    if key_event.kind == KeyEventKind::Press {
+        app.message = None;
+    }
+    if app.trainer.as_ref().is_some_and(|trainer| trainer.choosing) {
+        return handle_case_list_keys(key_event, app);
+    }
+    if app.detail.is_some() {
+        return handle_detail_keys(key_event, app);
+    }
+    if app.time_input.is_some() {
+        return handle_time_entry_keys(key_event, app);
+    }
+    if app.mbld.as_ref().is_some_and(|mbld| mbld.input.is_some()) {
+        return handle_mbld_keys(key_event, app);
+    }
+    if app.mode == Mode::Virtual {
+        return handle_virtual_keys(key_event, at, app);
+    }
 
-/// Handles the key events and updates the state of [`App`].
-pub fn handle_key_events(key_event: KeyEvent, app: &mut App) -> AppResult<()> {
     match key_event.code {
-        // Exit application on `ESC` or `q`
-        KeyCode::Esc | KeyCode::Char('q') => {
+        // Exit application on `Ctrl-C`
+        KeyCode::Char('c') | KeyCode::Char('C') if key_event.modifiers == KeyModifiers::CONTROL => {
             app.quit();
         }
-        // Exit application on `Ctrl-C`
-        KeyCode::Char('c') | KeyCode::Char('C') => {
-            if key_event.modifiers == KeyModifiers::CONTROL {
-                app.quit();
-            }
+        // space read from the keyboard has already arrived as a device event
+        KeyCode::Char(' ') if app.input == InputMode::Evdev => {}
+        // like a hardware timer, any key stops a running solve
+        _ if matches!(app.state, State::Timing | State::Stopped) => {
+            handle_space_key(key_event.kind, at, app);
         }
-        KeyCode::Char('h') | KeyCode::Char('H') => {
-            if key_event.kind == crossterm::event::KeyEventKind::Press {
-                app.toggle_help();
+        KeyCode::Char(' ') => handle_space_key(key_event.kind, at, app),
+        _ if key_event.kind == KeyEventKind::Press => {
+            if let Some(action) = app.config.keymap.action(&key_event) {
+                run_action(action, app);
             }
         }
+        _ => {}
+    }
+    Ok(())
+}
 
-        KeyCode::Char(' ') => {
-            if key_event.kind == crossterm::event::KeyEventKind::Release {
-                app.handle_space();
-                app.change_color(Color::White);
-            } else if key_event.kind == crossterm::event::KeyEventKind::Press {
-                if app.state != crate::app::State::Timing {
-                    app.change_color(Color::Green);
-                    app.show_help = false;
-                }
-            }
+/// Handles space, or while timing any key, as read in the app's input mode.
+fn handle_space_key(kind: KeyEventKind, at: Instant, app: &mut App) {
+    match (app.input, kind) {
+        (InputMode::Release, KeyEventKind::Press) => app.press_space(at),
+        (InputMode::Release, KeyEventKind::Release) => app.release_space(at),
+        (InputMode::Arm, KeyEventKind::Press) => app.arm_space(at),
+        (InputMode::Repeat, KeyEventKind::Press) => app.repeat_space(at),
+        // space is read from the keyboard, other keys still stop the timer,
+        // and as the terminal doesn't report them let go, go straight back
+        // to waiting
+        (InputMode::Evdev, KeyEventKind::Press) if app.state == State::Timing => {
+            app.press_space(at);
+            app.release_space(at);
         }
+        _ => {}
+    }
+}
+
+/// Does what a key is bound to, where it makes sense in the current mode.
+fn run_action(action: Action, app: &mut App) {
+    let idle = app.state == State::Idle;
+    match action {
+        Action::Quit => app.quit(),
+        Action::Help => app.toggle_help(),
+        Action::Scramble => app.new_scramble(),
+        Action::DeleteLast => app.times.del_last(),
+        Action::Reset if idle => app.reset(),
+        Action::LastScramble => app.show_last_scramble = !app.show_last_scramble,
+        Action::Dnf => app.times.toggle_penalty(Penalty::DNF),
+        Action::PlusTwo => app.times.toggle_penalty(Penalty::PlusTwo),
+        Action::Event if idle => app.next_variant(),
+        Action::Net => app.toggle_net(),
+        Action::Subset if idle => app.next_subset(),
+        Action::Trainer if idle && app.mode != Mode::Mbld => app.toggle_trainer(),
+        Action::Cases if idle && app.mode == Mode::Trainer => app.toggle_case_list(),
+        Action::Miss if idle && app.mode == Mode::Trainer => app.record_miss(),
+        Action::Inspection if idle && app.mode == Mode::Timer => app.next_inspection(),
+        Action::Virtual if idle && app.mode == Mode::Timer => app.toggle_virtual(),
+        Action::Newer if idle && app.mode == Mode::Timer => app.select_time(false),
+        Action::Older if idle && app.mode == Mode::Timer => app.select_time(true),
+        Action::Details if idle && app.mode == Mode::Timer => app.open_detail(),
+        Action::TypeTime if idle && app.mode == Mode::Timer => app.toggle_time_entry(),
+        Action::Mbld if idle && matches!(app.mode, Mode::Timer | Mode::Mbld) => app.toggle_mbld(),
+        _ => {}
+    }
+}
+
+/// Handles keys read straight from an input device, which only start and stop
+/// the timer; everything else still comes from the terminal. Keys typed into
+/// other windows while the terminal isn't focused are left alone.
+pub fn handle_device_events(key_event: KeyEvent, at: Instant, app: &mut App) -> AppResult<()> {
+    let popup = app.detail.is_some()
+        || app.time_input.is_some()
+        || app.mbld.as_ref().is_some_and(|mbld| mbld.input.is_some())
+        || app.trainer.as_ref().is_some_and(|trainer| trainer.choosing);
+    if app.input != InputMode::Evdev || !app.focused || app.mode == Mode::Virtual || popup {
+        return Ok(());
+    }
+    match (key_event.code, key_event.kind) {
+        (KeyCode::Char(' '), KeyEventKind::Press) => app.press_space(at),
+        (KeyCode::Char(' '), KeyEventKind::Release) => app.release_space(at),
+        _ => {}
+    }
+    Ok(())
+}
 
-        KeyCode::Char('s') | KeyCode::Char('S') => {
-            if key_event.kind == crossterm::event::KeyEventKind::Press {
-                app.new_scramble();
+/// Handles clicks and the scroll wheel, on the parts of the screen as last
+/// drawn. Rows of the times table open their details, the scramble gives a new
+/// one, and the detail popup has buttons for the solve's penalty.
+pub fn handle_mouse_events(mouse_event: MouseEvent, app: &mut App) -> AppResult<()> {
+    let click = mouse_event.kind == MouseEventKind::Down(MouseButton::Left);
+    if click {
+        app.message = None;
+    }
+    let over = |area: Rect| area.intersects(Rect::new(mouse_event.column, mouse_event.row, 1, 1));
+    let choosing = app.trainer.as_ref().is_some_and(|trainer| trainer.choosing);
+    let counting = app.mbld.as_ref().is_some_and(|mbld| mbld.input.is_some());
+    if app.show_help || app.time_input.is_some() || counting || choosing {
+        return Ok(());
+    }
+    if app.detail.is_some() {
+        let button = app.areas.penalties.iter().find(|(_, area)| over(*area));
+        if let (true, Some(&(penalty, _))) = (click, button) {
+            if app.reconstruction_input.is_none() {
+                app.set_detail_penalty(penalty);
             }
         }
+        return Ok(());
+    }
+    if app.state != State::Idle || app.mode == Mode::Virtual {
+        return Ok(());
+    }
 
-        KeyCode::Char('d') | KeyCode::Char('D') => {
-            if key_event.kind == crossterm::event::KeyEventKind::Press {
-                app.times.del_last();
+    let table = app.mode == Mode::Timer && over(app.areas.times);
+    match mouse_event.kind {
+        _ if click && over(app.areas.scramble) => app.new_scramble(),
+        // each row is followed by a blank line
+        _ if click && table => {
+            let row = (mouse_event.row - app.areas.times.y) as usize / 2;
+            let row = app.table_state.offset() + row;
+            if row < app.times.num_times() {
+                app.table_state.select(Some(row));
+                app.open_detail();
             }
         }
+        MouseEventKind::ScrollDown if table => app.select_time(true),
+        MouseEventKind::ScrollUp if table => app.select_time(false),
+        _ => {}
+    }
+    Ok(())
+}
 
-        KeyCode::Char('r') | KeyCode::Char('R') => {
-            if app.state == crate::app::State::Idle
-                && key_event.kind == crossterm::event::KeyEventKind::Press
-            {
-                app.reset();
+/// Handles the key events while the trainer's case list is open.
+fn handle_case_list_keys(key_event: KeyEvent, app: &mut App) -> AppResult<()> {
+    if key_event.kind != KeyEventKind::Press {
+        return Ok(());
+    }
+    let trainer = app.trainer.as_mut().unwrap();
+    match key_event.code {
+        KeyCode::Up | KeyCode::Char('k') => trainer.move_cursor(false),
+        KeyCode::Down | KeyCode::Char('j') => trainer.move_cursor(true),
+        KeyCode::Enter | KeyCode::Char(' ') => trainer.toggle_selected(),
+        KeyCode::Char('a') | KeyCode::Char('A') => trainer.toggle_all(),
+        KeyCode::Esc | KeyCode::Char('c') | KeyCode::Char('C') => app.toggle_case_list(),
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Handles the key events while the solve detail popup is open.
+fn handle_detail_keys(key_event: KeyEvent, app: &mut App) -> AppResult<()> {
+    if key_event.kind != KeyEventKind::Press {
+        return Ok(());
+    }
+    if let Some(input) = app.reconstruction_input.as_mut() {
+        match key_event.code {
+            KeyCode::Esc => app.reconstruction_input = None,
+            // a second Enter, on an empty line, saves
+            KeyCode::Enter if input.is_empty() || input.ends_with('\n') => {
+                app.save_reconstruction()
             }
+            KeyCode::Enter => input.push('\n'),
+            KeyCode::Backspace => {
+                input.pop();
+            }
+            KeyCode::Char(c) => input.push(c),
+            _ => {}
+        }
+        return Ok(());
+    }
+    match key_event.code {
+        KeyCode::Esc | KeyCode::Enter | KeyCode::Char('q') => app.close_detail(),
+        KeyCode::Char('c') | KeyCode::Char('C') if key_event.modifiers == KeyModifiers::CONTROL => {
+            app.quit();
         }
+        KeyCode::Char('e') | KeyCode::Char('E') => app.edit_reconstruction(),
+        KeyCode::Right => app.step_playback(true),
+        KeyCode::Left => app.step_playback(false),
+        _ => {}
+    }
+    Ok(())
+}
 
-        KeyCode::Char('l') | KeyCode::Char('L') => {
-            if key_event.kind == crossterm::event::KeyEventKind::Press {
-                app.show_last_scramble = !app.show_last_scramble;
-            }
+/// Handles the key events while a time is being typed in.
+fn handle_time_entry_keys(key_event: KeyEvent, app: &mut App) -> AppResult<()> {
+    if key_event.kind != KeyEventKind::Press {
+        return Ok(());
+    }
+    let input = app.time_input.as_mut().unwrap();
+    match key_event.code {
+        KeyCode::Char('c') | KeyCode::Char('C') if key_event.modifiers == KeyModifiers::CONTROL => {
+            app.quit();
+        }
+        KeyCode::Esc => app.toggle_time_entry(),
+        KeyCode::Enter => app.enter_time(),
+        KeyCode::Backspace => {
+            input.pop();
         }
+        KeyCode::Char(c) => input.push(c),
+        _ => {}
+    }
+    Ok(())
+}
 
-        KeyCode::Char('f') | KeyCode::Char('F') => {
-            if key_event.kind == crossterm::event::KeyEventKind::Press {
-                app.times.toggle_penalty(Penalty::DNF);
-            }
+/// Handles the key events while a count is typed in multi-blind mode.
+fn handle_mbld_keys(key_event: KeyEvent, app: &mut App) -> AppResult<()> {
+    if key_event.kind != KeyEventKind::Press {
+        return Ok(());
+    }
+    let input = app
+        .mbld
+        .as_mut()
+        .and_then(|mbld| mbld.input.as_mut())
+        .unwrap();
+    match key_event.code {
+        KeyCode::Char('c') | KeyCode::Char('C') if key_event.modifiers == KeyModifiers::CONTROL => {
+            app.quit();
         }
+        KeyCode::Esc => app.cancel_mbld(),
+        KeyCode::Enter => app.enter_mbld(),
+        KeyCode::Backspace => {
+            input.pop();
+        }
+        // only counts, so keys held from stopping the timer don't get in
+        KeyCode::Char(c) if c.is_ascii_digit() || c == '/' => input.push(c),
+        _ => {}
+    }
+    Ok(())
+}
 
-        KeyCode::Char('2') => {
-            if key_event.kind == crossterm::event::KeyEventKind::Press {
-                app.times.toggle_penalty(Penalty::PlusTwo);
+/// Handles the key events in virtual cube mode, where most letters turn the cube.
+fn handle_virtual_keys(key_event: KeyEvent, at: Instant, app: &mut App) -> AppResult<()> {
+    if key_event.kind != KeyEventKind::Press {
+        return Ok(());
+    }
+    match key_event.code {
+        KeyCode::Esc => app.toggle_virtual(),
+        KeyCode::Char('c') | KeyCode::Char('C') if key_event.modifiers == KeyModifiers::CONTROL => {
+            app.quit();
+        }
+        // give up on the attempt and start over with a new scramble
+        KeyCode::Char(' ') => app.restart_virtual(),
+        KeyCode::Char(key) => {
+            if let Some(m) = key_move(key.to_ascii_lowercase()) {
+                app.turn(m, at);
             }
         }
-
-        // Other handlers you could add here.
         _ => {}
     }
     Ok(())
@@ -0,0 +1,411 @@
+use crate::cube::Cube;
+use crate::cubie::*;
+use crate::solver::allowed_after;
+use crate::turns::{Direction, Move, Turn};
+use std::collections::VecDeque;
+use std::fmt::Display;
+use std::sync::{Once, OnceLock};
+use std::thread;
+use std::time::{Duration, Instant};
+
+// Optimal solutions of first steps (cross, Roux first block, EOLine). The
+// scrambled cube is rotated so the step is built on D, then only the pieces of
+// the step are tracked and searched with IDA*, using pattern databases of
+// groups of those pieces as the heuristic. The steps share one time budget,
+// and each search gives up once its share runs out.
+
+const N_MOVES: usize = 18;
+/// Positions times orientations, the same for corners (8 * 3) and edges (12 * 2).
+const N_CODES: usize = 24;
+/// Longest solution searched for; optimal first steps are much shorter.
+const MAX_DEPTH: usize = 14;
+
+/// First steps of a solve to give hints for.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Step {
+    /// Cross on the given face of the scrambled cube.
+    Cross(Direction),
+    /// Roux first block, on the left or right with white on D.
+    FirstBlock(Direction),
+    /// ZZ EOLine with white on D and green in front.
+    EoLine,
+}
+
+impl Step {
+    pub const ALL: [Step; 9] = [
+        Step::Cross(Direction::Up),
+        Step::Cross(Direction::Down),
+        Step::Cross(Direction::Front),
+        Step::Cross(Direction::Back),
+        Step::Cross(Direction::Right),
+        Step::Cross(Direction::Left),
+        Step::FirstBlock(Direction::Left),
+        Step::FirstBlock(Direction::Right),
+        Step::EoLine,
+    ];
+
+    /// Rotation that brings the face the step is built on to D.
+    pub fn rotation(&self) -> Option<Move> {
+        let face = match self {
+            Step::Cross(face) => *face,
+            Step::FirstBlock(_) | Step::EoLine => Direction::Up,
+        };
+        match face {
+            Direction::Up => Some(Move::rotation(Direction::Front, Turn::Double)),
+            Direction::Down => None,
+            Direction::Front => Some(Move::rotation(Direction::Right, Turn::CounterClockwise)),
+            Direction::Back => Some(Move::rotation(Direction::Right, Turn::Clockwise)),
+            Direction::Right => Some(Move::rotation(Direction::Front, Turn::Clockwise)),
+            Direction::Left => Some(Move::rotation(Direction::Front, Turn::CounterClockwise)),
+        }
+    }
+}
+
+/// Optimal solution of a step, `None` if none was found in time.
+#[derive(Debug, Clone)]
+pub struct Hint {
+    pub step: Step,
+    pub solution: Option<Vec<Move>>,
+}
+
+/// A tracked corner or edge piece.
+#[derive(Debug, Clone, Copy)]
+enum Piece {
+    Corner(usize),
+    Edge(usize),
+}
+
+/// Where each face turn sends the pieces: `(new position, orientation change)`
+/// by move and old position.
+struct PieceMoves {
+    corners: [[(u8, u8); 8]; N_MOVES],
+    edges: [[(u8, u8); 12]; N_MOVES],
+}
+
+impl PieceMoves {
+    fn new() -> Self {
+        let mut moves = Self {
+            corners: [[(0, 0); 8]; N_MOVES],
+            edges: [[(0, 0); 12]; N_MOVES],
+        };
+        for m in 0..N_MOVES {
+            let cube = move_cube(m);
+            for i in 0..8 {
+                moves.corners[m][cube.cp[i] as usize] = (i as u8, cube.co[i]);
+            }
+            for i in 0..12 {
+                moves.edges[m][cube.ep[i] as usize] = (i as u8, cube.eo[i]);
+            }
+        }
+        moves
+    }
+
+    /// Code (`position * orientations + orientation`) of a piece after move `m`.
+    fn apply(&self, piece: Piece, code: u8, m: usize) -> u8 {
+        match piece {
+            Piece::Corner(_) => {
+                let (to, twist) = self.corners[m][code as usize / 3];
+                to * 3 + (code % 3 + twist) % 3
+            }
+            Piece::Edge(_) => {
+                let (to, flip) = self.edges[m][code as usize / 2];
+                to * 2 + (code % 2 + flip) % 2
+            }
+        }
+    }
+
+    /// Orientation of every edge position after move `m`, as a bit mask.
+    fn apply_eo(&self, eo: u16, m: usize) -> u16 {
+        let mut result = 0;
+        for (from, &(to, flip)) in self.edges[m].iter().enumerate() {
+            let flipped = (eo >> from & 1) as u8 ^ flip;
+            result |= (flipped as u16) << to;
+        }
+        result
+    }
+}
+
+/// Code of a piece in its solved position.
+fn solved_code(piece: Piece) -> u8 {
+    match piece {
+        Piece::Corner(c) => c as u8 * 3,
+        Piece::Edge(e) => e as u8 * 2,
+    }
+}
+
+/// Code of a piece in a state.
+fn piece_code(cube: &CubieCube, piece: Piece) -> u8 {
+    match piece {
+        Piece::Corner(c) => {
+            let at = cube.cp.iter().position(|&p| p as usize == c).unwrap();
+            at as u8 * 3 + cube.co[at]
+        }
+        Piece::Edge(e) => {
+            let at = cube.ep.iter().position(|&p| p as usize == e).unwrap();
+            at as u8 * 2 + cube.eo[at]
+        }
+    }
+}
+
+/// Distance to solved of every placement of a group of pieces, indexed by
+/// their codes in base 24.
+fn piece_table(moves: &PieceMoves, group: &[Piece]) -> Vec<u8> {
+    let index = |codes: &[u8]| codes.iter().fold(0, |i, &c| i * N_CODES + c as usize);
+    let solved: Vec<u8> = group.iter().map(|&p| solved_code(p)).collect();
+    let mut table = vec![u8::MAX; N_CODES.pow(group.len() as u32)];
+    table[index(&solved)] = 0;
+    let mut queue = VecDeque::from([solved]);
+    while let Some(codes) = queue.pop_front() {
+        let distance = table[index(&codes)];
+        for m in 0..N_MOVES {
+            let next: Vec<u8> = group
+                .iter()
+                .zip(&codes)
+                .map(|(&piece, &code)| moves.apply(piece, code, m))
+                .collect();
+            if table[index(&next)] == u8::MAX {
+                table[index(&next)] = distance + 1;
+                queue.push_back(next);
+            }
+        }
+    }
+    table
+}
+
+/// Distance to all edges oriented of every edge orientation.
+fn eo_table(moves: &PieceMoves) -> Vec<u8> {
+    let mut table = vec![u8::MAX; 1 << 12];
+    table[0] = 0;
+    let mut queue = VecDeque::from([0]);
+    while let Some(eo) = queue.pop_front() {
+        for m in 0..N_MOVES {
+            let next = moves.apply_eo(eo, m);
+            if table[next as usize] == u8::MAX {
+                table[next as usize] = table[eo as usize] + 1;
+                queue.push_back(next);
+            }
+        }
+    }
+    table
+}
+
+/// Pieces of a step and the pattern databases of groups of them.
+struct Target {
+    pieces: Vec<Piece>,
+    /// Whether every edge must be oriented too.
+    eo: bool,
+    /// Groups as indices into `pieces`, with their tables.
+    groups: Vec<(Vec<usize>, Vec<u8>)>,
+}
+
+impl Target {
+    fn new(moves: &PieceMoves, pieces: Vec<Piece>, eo: bool, groups: &[&[usize]]) -> Self {
+        let groups = groups
+            .iter()
+            .map(|group| {
+                let group_pieces: Vec<Piece> = group.iter().map(|&i| pieces[i]).collect();
+                (group.to_vec(), piece_table(moves, &group_pieces))
+            })
+            .collect();
+        Self { pieces, eo, groups }
+    }
+}
+
+struct Tables {
+    moves: PieceMoves,
+    eo: Vec<u8>,
+    cross: Target,
+    left_block: Target,
+    right_block: Target,
+    eo_line: Target,
+}
+
+static TABLES: OnceLock<Tables> = OnceLock::new();
+
+fn tables() -> &'static Tables {
+    TABLES.get_or_init(|| {
+        use Piece::*;
+        let moves = PieceMoves::new();
+        let cross = Target::new(
+            &moves,
+            vec![Edge(DR), Edge(DF), Edge(DL), Edge(DB)],
+            false,
+            &[&[0, 1, 2, 3]],
+        );
+        let left_block = Target::new(
+            &moves,
+            vec![Corner(DLF), Corner(DBL), Edge(DL), Edge(FL), Edge(BL)],
+            false,
+            &[&[0, 1, 2], &[2, 3, 4], &[0, 3, 4], &[1, 3, 4]],
+        );
+        let right_block = Target::new(
+            &moves,
+            vec![Corner(DFR), Corner(DRB), Edge(DR), Edge(FR), Edge(BR)],
+            false,
+            &[&[0, 1, 2], &[2, 3, 4], &[0, 3, 4], &[1, 3, 4]],
+        );
+        let eo_line = Target::new(&moves, vec![Edge(DF), Edge(DB)], true, &[&[0, 1]]);
+        Tables {
+            eo: eo_table(&moves),
+            moves,
+            cross,
+            left_block,
+            right_block,
+            eo_line,
+        }
+    })
+}
+
+struct Search<'a> {
+    tables: &'a Tables,
+    target: &'a Target,
+    path: Vec<usize>,
+    deadline: Instant,
+    nodes: usize,
+    timed_out: bool,
+}
+
+impl<'a> Search<'a> {
+    fn distance(&self, codes: &[u8], eo: u16) -> usize {
+        let mut distance = match self.target.eo {
+            true => self.tables.eo[eo as usize] as usize,
+            false => 0,
+        };
+        for (group, table) in &self.target.groups {
+            let index = group
+                .iter()
+                .fold(0, |i, &p| i * N_CODES + codes[p] as usize);
+            distance = distance.max(table[index] as usize);
+        }
+        distance
+    }
+
+    fn search(&mut self, codes: &[u8], eo: u16, depth: usize) -> bool {
+        let distance = self.distance(codes, eo);
+        if distance == 0 {
+            return true;
+        }
+        if distance > depth {
+            return false;
+        }
+        self.nodes += 1;
+        if self.nodes % 4096 == 0 && Instant::now() > self.deadline {
+            self.timed_out = true;
+        }
+        if self.timed_out {
+            return false;
+        }
+
+        let moves = &self.tables.moves;
+        for m in 0..N_MOVES {
+            if !allowed_after(self.path.last().copied(), m) {
+                continue;
+            }
+            let next: Vec<u8> = self
+                .target
+                .pieces
+                .iter()
+                .zip(codes)
+                .map(|(&piece, &code)| moves.apply(piece, code, m))
+                .collect();
+            let next_eo = match self.target.eo {
+                true => moves.apply_eo(eo, m),
+                false => 0,
+            };
+            self.path.push(m);
+            if self.search(&next, next_eo, depth - 1) {
+                return true;
+            }
+            self.path.pop();
+        }
+        false
+    }
+}
+
+/// Optimal solution of `step` for the cube scrambled by `scramble`, after the
+/// step's rotation. Returns `None` if the scramble isn't a 3x3 scramble or no
+/// solution was found within `budget`.
+pub fn solve_step(scramble: &[Move], step: Step, budget: Duration) -> Option<Vec<Move>> {
+    let mut moves = scramble.to_vec();
+    moves.extend(step.rotation());
+    let cube = CubieCube::from_cube(&Cube::scrambled(3, &moves))?;
+
+    let tables = tables();
+    let target = match step {
+        Step::Cross(_) => &tables.cross,
+        Step::FirstBlock(Direction::Right) => &tables.right_block,
+        Step::FirstBlock(_) => &tables.left_block,
+        Step::EoLine => &tables.eo_line,
+    };
+    let codes: Vec<u8> = target
+        .pieces
+        .iter()
+        .map(|&piece| piece_code(&cube, piece))
+        .collect();
+    let eo = (0..12).fold(0, |eo, i| eo | (cube.eo[i] as u16) << i);
+
+    let mut search = Search {
+        tables,
+        target,
+        path: vec![],
+        deadline: Instant::now() + budget,
+        nodes: 0,
+        timed_out: false,
+    };
+    for depth in 0..=MAX_DEPTH {
+        if search.search(&codes, eo, depth) {
+            return Some(search.path.iter().map(|&m| index_move(m)).collect());
+        }
+        if search.timed_out {
+            return None;
+        }
+    }
+    None
+}
+
+/// Builds the pattern databases on a background thread, so hints don't wait
+/// for them. Does nothing after the first call.
+pub fn prepare() {
+    static STARTED: Once = Once::new();
+    STARTED.call_once(|| {
+        thread::spawn(tables);
+    });
+}
+
+/// Whether the pattern databases are built, so hints won't wait for them.
+pub fn ready() -> bool {
+    TABLES.get().is_some()
+}
+
+/// Hints for every step, all searched within `budget`. Each step gets an even
+/// share of what is left of it, so time a step doesn't need goes to the rest.
+pub fn hints(scramble: &[Move], budget: Duration) -> Vec<Hint> {
+    let deadline = Instant::now() + budget;
+    Step::ALL
+        .iter()
+        .enumerate()
+        .map(|(i, &step)| {
+            let left = deadline.saturating_duration_since(Instant::now());
+            Hint {
+                step,
+                solution: solve_step(scramble, step, left / (Step::ALL.len() - i) as u32),
+            }
+        })
+        .collect()
+}
+
+impl Display for Step {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Step::Cross(Direction::Up) => write!(f, "White cross"),
+            Step::Cross(Direction::Down) => write!(f, "Yellow cross"),
+            Step::Cross(Direction::Front) => write!(f, "Green cross"),
+            Step::Cross(Direction::Back) => write!(f, "Blue cross"),
+            Step::Cross(Direction::Right) => write!(f, "Red cross"),
+            Step::Cross(Direction::Left) => write!(f, "Orange cross"),
+            Step::FirstBlock(Direction::Right) => write!(f, "First block (right)"),
+            Step::FirstBlock(_) => write!(f, "First block (left)"),
+            Step::EoLine => write!(f, "EOLine"),
+        }
+    }
+}
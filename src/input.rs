@@ -0,0 +1,82 @@
+use crossterm::terminal;
+use std::time::{Duration, Instant};
+
+/// How space is read, depending on what the terminal reports.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum InputMode {
+    /// Space is held and let go, for terminals reporting key releases through
+    /// kitty's keyboard protocol.
+    Release,
+    /// One press gets the timer ready and the next starts it, for any terminal.
+    Arm,
+    /// Holding space is told apart from a tap by its key repeats, and letting
+    /// go by the repeats stopping.
+    Repeat,
+    /// Space is held and let go as read straight from the keyboards under
+    /// `/dev/input`, with the `evdev` feature.
+    Evdev,
+}
+
+impl InputMode {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "release" => Some(InputMode::Release),
+            "arm" => Some(InputMode::Arm),
+            "repeat" => Some(InputMode::Repeat),
+            "evdev" => Some(InputMode::Evdev),
+            _ => None,
+        }
+    }
+
+    /// The configured mode, or when there is none, key releases if the
+    /// terminal reports them and press to arm if not.
+    pub fn detect(configured: Option<Self>) -> Self {
+        configured.unwrap_or_else(|| match terminal::supports_keyboard_enhancement() {
+            Ok(true) => InputMode::Release,
+            _ => InputMode::Arm,
+        })
+    }
+}
+
+/// Infers space being held and let go from the presses of a terminal that
+/// doesn't report releases: a held key repeats, first after the keyboard's
+/// repeat delay and then every few tens of milliseconds.
+#[derive(Debug, Default)]
+pub struct RepeatTracker {
+    /// First and last press of the key being held.
+    held: Option<(Instant, Instant)>,
+}
+
+impl RepeatTracker {
+    /// Longest wait for the first repeat, a little over common repeat delays.
+    pub const FIRST_REPEAT: Duration = Duration::from_millis(700);
+    /// Longest wait between later repeats.
+    pub const REPEAT_GAP: Duration = Duration::from_millis(150);
+
+    /// Records a press at `at`, returning whether it starts a new hold rather
+    /// than repeating the one going on.
+    pub fn press(&mut self, at: Instant) -> bool {
+        let repeat = self.released(at).is_none() && self.held.is_some();
+        self.held = match self.held {
+            Some((first, _)) if repeat => Some((first, at)),
+            _ => Some((at, at)),
+        };
+        !repeat
+    }
+
+    /// Whether the key has been let go by `now`, going by how long since the
+    /// last press, giving the time of that press if so.
+    pub fn released(&mut self, now: Instant) -> Option<Instant> {
+        let (first, last) = self.held?;
+        let gap = match first == last {
+            true => Self::FIRST_REPEAT,
+            false => Self::REPEAT_GAP,
+        };
+        if now.saturating_duration_since(last) > gap {
+            self.held = None;
+            Some(last)
+        } else {
+            None
+        }
+    }
+}
@@ -0,0 +1,109 @@
+use crate::app::Penalty;
+use crate::scramble::Variant;
+use serde_json::Value;
+use std::fmt::Display;
+use std::time::Duration;
+
+/// Inspection before a solve.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Inspection {
+    /// The timer starts straight away, as for blindfolded events.
+    None,
+    /// 15 seconds, as in WCA regulation A3a.
+    Wca,
+    /// A custom number of seconds, with penalties following the WCA ones.
+    Custom(u64),
+}
+
+impl Inspection {
+    /// Inspection used for a variant until the session picks another.
+    pub fn default_for(variant: Variant) -> Self {
+        match variant {
+            Variant::ThreeBld | Variant::FourBld | Variant::FiveBld => Inspection::None,
+            _ => Inspection::Wca,
+        }
+    }
+
+    /// Reads inspection as stored in the session: `"none"`, `"wca"` or a
+    /// number of seconds.
+    pub fn from_value(json: &Value) -> Option<Self> {
+        match json {
+            Value::String(id) if id == "none" => Some(Inspection::None),
+            Value::String(id) if id == "wca" => Some(Inspection::Wca),
+            Value::Number(seconds) => seconds.as_u64().map(Inspection::Custom),
+            _ => None,
+        }
+    }
+
+    pub fn to_value(self) -> Value {
+        match self {
+            Inspection::None => Value::from("none"),
+            Inspection::Wca => Value::from("wca"),
+            Inspection::Custom(seconds) => Value::from(seconds),
+        }
+    }
+
+    /// The inspection after this one: none, WCA, then `custom` seconds.
+    pub fn next(&self, custom: u64) -> Self {
+        match self {
+            Inspection::None => Inspection::Wca,
+            Inspection::Wca => Inspection::Custom(custom),
+            Inspection::Custom(_) => Inspection::None,
+        }
+    }
+
+    /// Seconds of inspection, `None` without inspection.
+    pub fn seconds(&self) -> Option<u64> {
+        match self {
+            Inspection::None => None,
+            Inspection::Wca => Some(15),
+            Inspection::Custom(seconds) => Some(*seconds),
+        }
+    }
+
+    /// Penalty for starting after inspecting for `elapsed`: +2 once the time
+    /// is up, DNF two seconds later.
+    pub fn penalty(&self, elapsed: Duration) -> Penalty {
+        let Some(seconds) = self.seconds() else {
+            return Penalty::None;
+        };
+        let over = elapsed.as_secs_f64() - seconds as f64;
+        if over > 2.0 {
+            Penalty::DNF
+        } else if over > 0.0 {
+            Penalty::PlusTwo
+        } else {
+            Penalty::None
+        }
+    }
+
+    /// Warnings called out while inspecting, as seconds elapsed: 8 and 12 for
+    /// WCA inspection, the same distance from the end for custom lengths.
+    pub fn warnings(&self) -> Vec<u64> {
+        match self.seconds() {
+            Some(seconds) => [7, 3]
+                .iter()
+                .filter(|&&left| seconds > left)
+                .map(|left| seconds - left)
+                .collect(),
+            None => vec![],
+        }
+    }
+
+    /// The last warning reached after inspecting for `elapsed`.
+    pub fn warning(&self, elapsed: Duration) -> Option<u64> {
+        self.warnings()
+            .into_iter()
+            .rfind(|&warning| elapsed.as_secs_f64() >= warning as f64)
+    }
+}
+
+impl Display for Inspection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Inspection::None => write!(f, "no inspection"),
+            Inspection::Wca => write!(f, "15s inspection"),
+            Inspection::Custom(seconds) => write!(f, "{}s inspection", seconds),
+        }
+    }
+}
@@ -0,0 +1,292 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use serde_json::{Map, Value};
+
+/// Something a key does while not timing.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Action {
+    Quit,
+    Help,
+    Scramble,
+    DeleteLast,
+    Reset,
+    LastScramble,
+    Dnf,
+    PlusTwo,
+    Event,
+    Net,
+    Subset,
+    Trainer,
+    Cases,
+    Miss,
+    Inspection,
+    Virtual,
+    Newer,
+    Older,
+    Details,
+    TypeTime,
+    Mbld,
+}
+
+impl Action {
+    /// Every action, in the order the help lists them.
+    pub const ALL: [Action; 21] = [
+        Action::Quit,
+        Action::Help,
+        Action::Scramble,
+        Action::DeleteLast,
+        Action::Reset,
+        Action::LastScramble,
+        Action::Dnf,
+        Action::PlusTwo,
+        Action::Event,
+        Action::Net,
+        Action::Subset,
+        Action::Trainer,
+        Action::Cases,
+        Action::Miss,
+        Action::Newer,
+        Action::Older,
+        Action::Details,
+        Action::TypeTime,
+        Action::Mbld,
+        Action::Inspection,
+        Action::Virtual,
+    ];
+
+    /// Name of the action in the config's `keys`.
+    pub fn id(self) -> &'static str {
+        match self {
+            Action::Quit => "quit",
+            Action::Help => "help",
+            Action::Scramble => "scramble",
+            Action::DeleteLast => "delete_last",
+            Action::Reset => "reset",
+            Action::LastScramble => "last_scramble",
+            Action::Dnf => "dnf",
+            Action::PlusTwo => "plus_two",
+            Action::Event => "event",
+            Action::Net => "net",
+            Action::Subset => "subset",
+            Action::Trainer => "trainer",
+            Action::Cases => "cases",
+            Action::Miss => "miss",
+            Action::Inspection => "inspection",
+            Action::Virtual => "virtual",
+            Action::Newer => "newer",
+            Action::Older => "older",
+            Action::Details => "details",
+            Action::TypeTime => "type_time",
+            Action::Mbld => "mbld",
+        }
+    }
+
+    /// What the action does, as the help puts it.
+    pub fn description(self) -> &'static str {
+        match self {
+            Action::Quit => "Quit",
+            Action::Help => "Toggle help",
+            Action::Scramble => "New scramble",
+            Action::DeleteLast => "Delete last time",
+            Action::Reset => "Reset",
+            Action::LastScramble => "Toggle last scramble",
+            Action::Dnf => "Add/remove DNF penalty to last time",
+            Action::PlusTwo => "Add/remove +2 penalty to last time",
+            Action::Event => "Switch event",
+            Action::Net => "Toggle scramble preview",
+            Action::Subset => "Switch training subset (3x3)",
+            Action::Trainer => "Toggle algorithm trainer",
+            Action::Cases => "Choose trainer cases",
+            Action::Miss => "Mark last case as not recognised",
+            Action::Inspection => "Switch inspection (none, 15s, custom)",
+            Action::Virtual => "Virtual cube (csTimer keys, Esc to leave)",
+            Action::Newer => "Select a newer time",
+            Action::Older => "Select an older time",
+            Action::Details => "Show the selected time's details",
+            Action::TypeTime => "Type in a time, e.g. from a stackmat",
+            Action::Mbld => "Toggle multi-blind attempts",
+        }
+    }
+
+    fn default_keys(self) -> &'static [&'static str] {
+        match self {
+            Action::Quit => &["q", "Esc"],
+            Action::Help => &["h"],
+            Action::Scramble => &["s"],
+            Action::DeleteLast => &["d"],
+            Action::Reset => &["r"],
+            Action::LastScramble => &["l"],
+            Action::Dnf => &["f"],
+            Action::PlusTwo => &["2"],
+            Action::Event => &["e"],
+            Action::Net => &["n"],
+            Action::Subset => &["t"],
+            Action::Trainer => &["a"],
+            Action::Cases => &["c"],
+            Action::Miss => &["x"],
+            Action::Inspection => &["i"],
+            Action::Virtual => &["v"],
+            Action::Newer => &["Up"],
+            Action::Older => &["Down"],
+            Action::Details => &["Enter"],
+            Action::TypeTime => &["m"],
+            Action::Mbld => &["b"],
+        }
+    }
+}
+
+/// Keys bound to each action.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Keymap {
+    bindings: Vec<(Action, Vec<KeyCode>)>,
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        let bindings = Action::ALL
+            .iter()
+            .map(|&action| {
+                let keys = action.default_keys().iter();
+                (action, keys.filter_map(|name| parse_key(name)).collect())
+            })
+            .collect();
+        Self { bindings }
+    }
+}
+
+impl Keymap {
+    /// Reads the config's `keys`, e.g. `{"scramble": ["s", "F5"]}`. Actions
+    /// left out keep their default keys. Fails on unknown actions or keys,
+    /// space, which is the timer's, and keys bound to more than one action.
+    pub fn from_value(json: &Value) -> Result<Self, String> {
+        let mut keymap = Self::default();
+        let Some(keys) = json.as_object() else {
+            return Ok(keymap);
+        };
+        for (id, names) in keys {
+            let action = Action::ALL
+                .into_iter()
+                .find(|action| action.id() == id)
+                .ok_or(format!("unknown action {}", id))?;
+            let names = names
+                .as_array()
+                .ok_or(format!("keys for {} should be a list", id))?;
+            let mut codes = vec![];
+            for name in names {
+                let name = name.as_str().unwrap_or_default();
+                match parse_key(name) {
+                    Some(KeyCode::Char(' ')) => {
+                        return Err("space is the timer's key".to_string());
+                    }
+                    Some(code) => codes.push(code),
+                    None => return Err(format!("unknown key \"{}\" for {}", name, id)),
+                }
+            }
+            keymap.bindings[action_index(action)].1 = codes;
+        }
+
+        // every key does one thing
+        for (i, (action, codes)) in keymap.bindings.iter().enumerate() {
+            for code in codes {
+                let other = keymap.bindings[i + 1..]
+                    .iter()
+                    .find(|(_, others)| others.contains(code));
+                if let Some((other, _)) = other {
+                    return Err(format!(
+                        "{} is bound to both {} and {}",
+                        key_name(*code),
+                        action.id(),
+                        other.id()
+                    ));
+                }
+            }
+        }
+        Ok(keymap)
+    }
+
+    /// The bindings as written in the config.
+    pub fn to_value(&self) -> Value {
+        let mut keys = Map::new();
+        for (action, codes) in &self.bindings {
+            let names: Vec<Value> = codes.iter().map(|&code| key_name(code).into()).collect();
+            keys.insert(action.id().to_string(), names.into());
+        }
+        Value::Object(keys)
+    }
+
+    /// The action bound to a key, letters matching either case. Keys held
+    /// with Ctrl or Alt aren't bound.
+    pub fn action(&self, key_event: &KeyEvent) -> Option<Action> {
+        if key_event
+            .modifiers
+            .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT)
+        {
+            return None;
+        }
+        let code = match key_event.code {
+            KeyCode::Char(c) => KeyCode::Char(c.to_ascii_lowercase()),
+            code => code,
+        };
+        self.bindings
+            .iter()
+            .find(|(_, codes)| codes.contains(&code))
+            .map(|(action, _)| *action)
+    }
+
+    /// Keys bound to an action, e.g. "q, Esc".
+    pub fn describe(&self, action: Action) -> String {
+        let names: Vec<String> = self.bindings[action_index(action)]
+            .1
+            .iter()
+            .map(|&code| key_name(code))
+            .collect();
+        names.join(", ")
+    }
+}
+
+fn action_index(action: Action) -> usize {
+    Action::ALL.iter().position(|&a| a == action).unwrap()
+}
+
+/// Reads a key name: a character, e.g. `s`, or one of `Esc`, `Enter`, `Tab`,
+/// `Backspace`, `Delete`, `Insert`, `Home`, `End`, `PageUp`, `PageDown`, the
+/// arrows `Up`, `Down`, `Left` and `Right`, or `F1` to `F12`.
+pub fn parse_key(name: &str) -> Option<KeyCode> {
+    let mut chars = name.chars();
+    if let (Some(c), None) = (chars.next(), chars.next()) {
+        return Some(KeyCode::Char(c.to_ascii_lowercase()));
+    }
+    let code = match name.to_ascii_lowercase().as_str() {
+        "space" => KeyCode::Char(' '),
+        "esc" => KeyCode::Esc,
+        "enter" => KeyCode::Enter,
+        "tab" => KeyCode::Tab,
+        "backspace" => KeyCode::Backspace,
+        "delete" => KeyCode::Delete,
+        "insert" => KeyCode::Insert,
+        "home" => KeyCode::Home,
+        "end" => KeyCode::End,
+        "pageup" => KeyCode::PageUp,
+        "pagedown" => KeyCode::PageDown,
+        "up" => KeyCode::Up,
+        "down" => KeyCode::Down,
+        "left" => KeyCode::Left,
+        "right" => KeyCode::Right,
+        name => match name.strip_prefix('f').and_then(|n| n.parse().ok()) {
+            Some(n @ 1..=12) => KeyCode::F(n),
+            _ => return None,
+        },
+    };
+    Some(code)
+}
+
+/// Name of a key, as [`parse_key`] reads it.
+pub fn key_name(code: KeyCode) -> String {
+    match code {
+        KeyCode::Char(' ') => "Space".to_string(),
+        KeyCode::Char(c) => c.to_string(),
+        KeyCode::F(n) => format!("F{}", n),
+        KeyCode::PageUp => "PageUp".to_string(),
+        KeyCode::PageDown => "PageDown".to_string(),
+        code => format!("{:?}", code),
+    }
+}
@@ -24,3 +24,63 @@ pub mod turns;
 
 /// Times
 pub mod times;
+
+/// Notation parsing
+pub mod notation;
+
+/// Cube model
+pub mod cube;
+
+/// Cube net widget
+pub mod net;
+
+/// Settings
+pub mod config;
+
+/// Scramble sets
+pub mod scramble_set;
+
+/// Cubie model
+pub mod cubie;
+
+/// Two-phase solver
+pub mod solver;
+
+/// Training subsets
+pub mod subset;
+
+/// Algorithm trainer
+pub mod trainer;
+
+/// Spaced repetition
+pub mod schedule;
+
+/// First step hints
+pub mod hints;
+
+/// Virtual cube
+pub mod virtual_cube;
+
+/// Solve reconstructions
+pub mod reconstruction;
+
+/// Inspection settings
+pub mod inspection;
+
+/// Key input modes
+pub mod input;
+
+/// Raw keyboard input
+pub mod evdev;
+
+/// Clocks
+pub mod clock;
+
+/// Key bindings
+pub mod keymap;
+
+/// Typed times
+pub mod entry;
+
+/// Multi-blind attempts
+pub mod mbld;
@@ -1,36 +1,101 @@
 use cube_timer::app::{App, AppResult};
-use cube_timer::event::{Event, EventHandler};
-use cube_timer::handler::handle_key_events;
+use cube_timer::evdev;
+use cube_timer::event::EventHandler;
+use cube_timer::handler::handle_event;
+use cube_timer::input::InputMode;
+use cube_timer::scramble_set::ScrambleSet;
+use cube_timer::trainer::Trainer;
 use cube_timer::tui::Tui;
-use std::io;
+use std::path::PathBuf;
+use std::{env, io};
 use tui::backend::CrosstermBackend;
 use tui::Terminal;
 
+/// Command line options.
+#[derive(Debug, Default)]
+struct Args {
+    /// Seed for the scramble generator, e.g. `--seed 42`.
+    seed: Option<u64>,
+    /// File of pre-generated scrambles, e.g. `--scrambles club.txt`.
+    scrambles: Option<PathBuf>,
+    /// File of algorithm cases to train, e.g. `--cases oll.json`.
+    cases: Option<PathBuf>,
+}
+
+impl Args {
+    fn parse() -> AppResult<Self> {
+        let mut args = Self::default();
+        let mut iter = env::args().skip(1);
+        while let Some(arg) = iter.next() {
+            // options are given as `--name value` or `--name=value`
+            let (name, value) = match arg.split_once('=') {
+                Some((name, value)) => (name.to_string(), Some(value.to_string())),
+                None => (arg, None),
+            };
+            let mut value = || {
+                value
+                    .clone()
+                    .or_else(|| iter.next())
+                    .ok_or(format!("{} needs a value", name))
+            };
+            match name.as_str() {
+                "--seed" => args.seed = Some(value()?.parse()?),
+                "--scrambles" => args.scrambles = Some(PathBuf::from(value()?)),
+                "--cases" => args.cases = Some(PathBuf::from(value()?)),
+                _ => return Err(format!("unknown argument: {}", name).into()),
+            }
+        }
+        Ok(args)
+    }
+}
+
 fn main() -> AppResult<()> {
+    let args = Args::parse()?;
+
     // Create an application.
-    let mut app = App::new();
+    let mut app = match args.seed {
+        Some(seed) => App::with_seed(seed),
+        None => App::new(),
+    };
+    if let Some(path) = args.scrambles {
+        app.load_scramble_set(ScrambleSet::load(&path)?);
+    }
+    if let Some(path) = args.cases {
+        app.load_trainer(Trainer::load(&path)?);
+    }
+    // Fall back to pressing space without holding it if key releases aren't reported.
+    app.input = InputMode::detect(app.config.input);
 
     // Initialize the terminal user interface.
     let backend = CrosstermBackend::new(io::stderr());
     let terminal = Terminal::new(backend)?;
-    let events = EventHandler::new(100);
+    let events = EventHandler::with_clock(100, app.clock.clone());
+    if app.input == InputMode::Evdev {
+        // read space from the keyboards, or from the terminal if they can't be read
+        match evdev::open_keyboards() {
+            Ok(keyboards) => keyboards.into_iter().for_each(|kbd| events.add_source(kbd)),
+            Err(e) => {
+                app.message = Some(format!("Can't read keyboards: {}", e));
+                app.input = InputMode::detect(None);
+            }
+        }
+    }
     let mut tui = Tui::new(terminal, events);
-    tui.init()?;
+    tui.init(app.input)?;
 
-    // Start the main loop.
-    while app.running {
+    // Start the main loop, until quitting or the terminal can't be read.
+    let mut result = Ok(());
+    while app.running && result.is_ok() {
         // Render the user interface.
         tui.draw(&mut app)?;
         // Handle events.
-        match tui.events.next()? {
-            Event::Tick => app.tick(),
-            Event::Key(key_event) => handle_key_events(key_event, &mut app)?,
-            Event::Mouse(_) => {}
-            Event::Resize(_, _) => {}
-        }
+        result = tui
+            .events
+            .next()
+            .and_then(|event| handle_event(event, &mut app));
     }
 
-    // Exit the user interface.
+    // Exit the user interface, then report why if it wasn't quit.
     tui.exit()?;
-    Ok(())
+    result
 }
@@ -0,0 +1,160 @@
+use crate::scramble::{Scramble, Variant};
+use rand::Rng;
+use serde_json::{json, Value};
+use std::cmp::Ordering;
+use std::fmt::Display;
+use std::time::Duration;
+
+/// Time allowed for each cube of an attempt.
+pub const TIME_PER_CUBE: Duration = Duration::from_secs(10 * 60);
+/// Time allowed for an attempt, however many cubes it has.
+pub const MAX_TIME: Duration = Duration::from_secs(60 * 60);
+/// Most cubes an attempt can be scrambled for.
+pub const MAX_CUBES: usize = 100;
+
+/// A multi-blind attempt: the cubes' scrambles, and the count being typed, of
+/// cubes to scramble before the attempt or of cubes solved after it.
+#[derive(Debug, Clone)]
+pub struct Mbld {
+    pub scrambles: Vec<Scramble>,
+    /// Count being typed, `None` from scrambling until the attempt is over.
+    pub input: Option<String>,
+    /// Seconds the attempt took, once over and until its counts are typed.
+    pub time: Option<f64>,
+}
+
+impl Default for Mbld {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Mbld {
+    /// An attempt that asks for the number of cubes first.
+    pub fn new() -> Self {
+        Self {
+            scrambles: vec![],
+            input: Some(String::new()),
+            time: None,
+        }
+    }
+
+    pub fn cubes(&self) -> usize {
+        self.scrambles.len()
+    }
+
+    /// Scrambles `cubes` cubes afresh, as for 3BLD.
+    pub fn scramble<R: Rng + ?Sized>(&mut self, cubes: usize, rng: &mut R) {
+        self.scrambles = (0..cubes)
+            .map(|_| Scramble::for_variant(Variant::ThreeBld, rng))
+            .collect();
+    }
+
+    /// Time allowed for the attempt: 10 minutes a cube, an hour at most.
+    pub fn time_limit(&self) -> Duration {
+        (TIME_PER_CUBE * self.cubes() as u32).min(MAX_TIME)
+    }
+}
+
+/// Reads the number of cubes to scramble for an attempt.
+pub fn parse_cubes(text: &str) -> Result<usize, String> {
+    match text.trim().parse() {
+        Ok(cubes @ 2..=MAX_CUBES) => Ok(cubes),
+        _ => Err(format!("An attempt has 2 to {} cubes", MAX_CUBES)),
+    }
+}
+
+/// Result of a multi-blind attempt.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MbldResult {
+    pub solved: usize,
+    pub attempted: usize,
+    /// Seconds taken, memorisation included.
+    pub time: f64,
+}
+
+impl MbldResult {
+    /// Reads the counts typed after an attempt at `cubes` cubes taking `time`:
+    /// solved and attempted, e.g. `5/6`, or only solved when every cube was
+    /// attempted.
+    pub fn parse(text: &str, cubes: usize, time: f64) -> Result<Self, String> {
+        let text = text.trim();
+        let (solved, attempted) = match text.split_once('/') {
+            Some((solved, attempted)) => (solved.parse(), attempted.parse()),
+            None => (text.parse(), Ok(cubes)),
+        };
+        let (Ok(solved), Ok(attempted)) = (solved, attempted) else {
+            return Err(format!("\"{}\" isn't solved/attempted, e.g. 5/6", text));
+        };
+        if !(2..=cubes).contains(&attempted) {
+            return Err(format!("Attempted has to be 2 to {} cubes", cubes));
+        }
+        if solved > attempted {
+            return Err("More cubes solved than attempted".to_string());
+        }
+        Ok(Self {
+            solved,
+            attempted,
+            time,
+        })
+    }
+
+    /// Cubes solved less cubes not solved.
+    pub fn points(&self) -> i64 {
+        2 * self.solved as i64 - self.attempted as i64
+    }
+
+    /// Whether the attempt counts as a DNF: less than 0 points, or fewer than
+    /// 2 cubes solved.
+    pub fn dnf(&self) -> bool {
+        self.points() < 0 || self.solved < 2
+    }
+
+    /// How the attempt ranks against another, better first: DNFs last, then
+    /// more points, less time, and fewer cubes missed.
+    pub fn rank(&self, other: &Self) -> Ordering {
+        let missed = |result: &Self| result.attempted - result.solved;
+        self.dnf()
+            .cmp(&other.dnf())
+            .then(other.points().cmp(&self.points()))
+            .then(self.time.total_cmp(&other.time))
+            .then(missed(self).cmp(&missed(other)))
+    }
+
+    /// Reads an attempt as stored in the session.
+    pub fn from_value(json: &Value) -> Option<Self> {
+        Some(Self {
+            solved: json["solved"].as_u64()? as usize,
+            attempted: json["attempted"].as_u64()? as usize,
+            time: json["time"].as_f64()?,
+        })
+    }
+
+    /// The attempt as stored in the session, with its points and penalty
+    /// spelled out.
+    pub fn to_value(self) -> Value {
+        json!({
+            "solved": self.solved,
+            "attempted": self.attempted,
+            "time": self.time,
+            "points": self.points(),
+            "penalty": if self.dnf() { "DNF" } else { "" },
+        })
+    }
+}
+
+impl Display for MbldResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let result = format!("{}/{} {}", self.solved, self.attempted, clock(self.time));
+        match self.dnf() {
+            true => write!(f, "DNF ({})", result),
+            false => write!(f, "{}", result),
+        }
+    }
+}
+
+/// Seconds as minutes and whole seconds, e.g. `41:02`.
+pub fn clock(seconds: f64) -> String {
+    let seconds = seconds as u64;
+    format!("{}:{:02}", seconds / 60, seconds % 60)
+}
@@ -0,0 +1,99 @@
+use tui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Style},
+    widgets::{Block, Widget},
+};
+
+use crate::config::ColourScheme;
+use crate::cube::Cube;
+use crate::turns::Direction;
+
+/// Unfolded cube net, drawn with coloured cells.
+///
+/// Stickers are scaled to the largest size that fits the area, keeping them
+/// roughly square on screen (two columns per row).
+pub struct Net<'a> {
+    cube: &'a Cube,
+    scheme: ColourScheme,
+    block: Option<Block<'a>>,
+}
+
+impl<'a> Net<'a> {
+    pub fn new(cube: &'a Cube, scheme: ColourScheme) -> Self {
+        Self {
+            cube,
+            scheme,
+            block: None,
+        }
+    }
+
+    pub fn block(mut self, block: Block<'a>) -> Self {
+        self.block = Some(block);
+        self
+    }
+}
+
+/// Column and row of each face in the 4x3 grid of faces.
+fn net_position(face: Direction) -> (u16, u16) {
+    match face {
+        Direction::Up => (1, 0),
+        Direction::Left => (0, 1),
+        Direction::Front => (1, 1),
+        Direction::Right => (2, 1),
+        Direction::Back => (3, 1),
+        Direction::Down => (1, 2),
+    }
+}
+
+impl<'a> Widget for Net<'a> {
+    fn render(mut self, area: Rect, buf: &mut Buffer) {
+        let area = match self.block.take() {
+            Some(block) => {
+                let inner = block.inner(area);
+                block.render(area, buf);
+                inner
+            }
+            None => area,
+        };
+
+        // stickers are twice as wide as they are high, plus a column gap
+        // between them, shrinking to single cells on small terminals
+        let n = self.cube.size as u16;
+        let height = (area.height / (3 * n)).min((area.width / (4 * n)).saturating_sub(1) / 2);
+        let (cell_width, cell_height, gap) = match height {
+            0 if area.width >= 8 * n && area.height >= 3 * n => (2, 1, 1),
+            0 if area.width >= 4 * n && area.height >= 3 * n => (1, 1, 0),
+            _ if area.area() == 0 => return,
+            0 => {
+                buf.set_string(area.x, area.y, "Too small", Style::default());
+                return;
+            }
+            h => (2 * h + 1, h, 1),
+        };
+
+        let net_width = 4 * n * cell_width;
+        let net_height = 3 * n * cell_height;
+        let x0 = area.x + (area.width - net_width) / 2;
+        let y0 = area.y + (area.height - net_height) / 2;
+
+        for face in crate::cube::FACES {
+            let (face_col, face_row) = net_position(face);
+            for (row, stickers) in self.cube.face(face).iter().enumerate() {
+                for (col, &colour) in stickers.iter().enumerate() {
+                    let x = x0 + (face_col * n + col as u16) * cell_width;
+                    let y = y0 + (face_row * n + row as u16) * cell_height;
+                    for dy in 0..cell_height {
+                        for dx in 0..cell_width {
+                            let bg = match dx + gap < cell_width {
+                                true => self.scheme.colour(colour),
+                                false => Color::Reset,
+                            };
+                            buf.get_mut(x + dx, y + dy).set_symbol(" ").set_bg(bg);
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
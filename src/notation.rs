@@ -0,0 +1,195 @@
+use crate::scramble::Scramble;
+use crate::turns::{Direction, Layers, Move, Turn};
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// Error from parsing move notation.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct ParseError {
+    /// Character offset of the problem in the parsed string.
+    pub position: usize,
+    pub message: String,
+}
+
+impl ParseError {
+    fn new(position: usize, message: impl Into<String>) -> Self {
+        ParseError {
+            position,
+            message: message.into(),
+        }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} at position {}", self.message, self.position)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Parses WCA notation into moves.
+///
+/// Accepts face turns (`R`, `U'`, `F2`), wide moves (`Rw`, `3Rw`, `r`),
+/// slices (`M`, `E`, `S`) and rotations (`x`, `y`, `z`), each optionally
+/// followed by `2`, `'` or `2'`. Moves may be separated by whitespace or
+/// written together (`RUR'U'`).
+pub fn parse_moves(notation: &str) -> Result<Vec<Move>, ParseError> {
+    let chars: Vec<char> = notation.chars().collect();
+    let mut moves = vec![];
+    let mut pos = 0;
+
+    while pos < chars.len() {
+        if chars[pos].is_whitespace() {
+            pos += 1;
+            continue;
+        }
+        let start = pos;
+
+        // layer count of a wide move, e.g. the 3 in 3Rw
+        let mut depth = None;
+        while pos < chars.len() && chars[pos].is_ascii_digit() {
+            let digit = chars[pos].to_digit(10).unwrap() as u8;
+            depth = Some(
+                depth
+                    .unwrap_or(0u8)
+                    .saturating_mul(10)
+                    .saturating_add(digit),
+            );
+            pos += 1;
+        }
+
+        let Some(&face) = chars.get(pos) else {
+            return Err(ParseError::new(
+                pos,
+                "expected a move after the layer count",
+            ));
+        };
+        let (direction, mut layers) = match face {
+            'U' => (Direction::Up, Layers::Outer),
+            'D' => (Direction::Down, Layers::Outer),
+            'L' => (Direction::Left, Layers::Outer),
+            'R' => (Direction::Right, Layers::Outer),
+            'F' => (Direction::Front, Layers::Outer),
+            'B' => (Direction::Back, Layers::Outer),
+            'u' => (Direction::Up, Layers::Wide(2)),
+            'd' => (Direction::Down, Layers::Wide(2)),
+            'l' => (Direction::Left, Layers::Wide(2)),
+            'r' => (Direction::Right, Layers::Wide(2)),
+            'f' => (Direction::Front, Layers::Wide(2)),
+            'b' => (Direction::Back, Layers::Wide(2)),
+            'M' => (Direction::Left, Layers::Slice),
+            'E' => (Direction::Down, Layers::Slice),
+            'S' => (Direction::Front, Layers::Slice),
+            'x' => (Direction::Right, Layers::Rotation),
+            'y' => (Direction::Up, Layers::Rotation),
+            'z' => (Direction::Front, Layers::Rotation),
+            other => {
+                return Err(ParseError::new(
+                    pos,
+                    format!("unexpected character '{}'", other),
+                ))
+            }
+        };
+        pos += 1;
+
+        if chars.get(pos) == Some(&'w') {
+            if layers != Layers::Outer {
+                return Err(ParseError::new(pos, format!("'{}' can't be wide", face)));
+            }
+            layers = Layers::Wide(2);
+            pos += 1;
+        }
+
+        if let Some(depth) = depth {
+            if layers != Layers::Wide(2) || face.is_lowercase() {
+                return Err(ParseError::new(
+                    start,
+                    "a layer count needs a wide move, e.g. 3Rw",
+                ));
+            }
+            if depth < 2 {
+                return Err(ParseError::new(
+                    start,
+                    "a wide move turns at least 2 layers",
+                ));
+            }
+            layers = Layers::Wide(depth);
+        }
+
+        let mut turn = Turn::Clockwise;
+        if chars.get(pos) == Some(&'2') {
+            turn = Turn::Double;
+            pos += 1;
+        }
+        if matches!(chars.get(pos), Some('\'') | Some('’')) {
+            turn = turn.opposite();
+            pos += 1;
+        }
+
+        // the next move has to start on its own, e.g. R3 or R'' is an error
+        if let Some(&next) = chars.get(pos) {
+            if next.is_ascii_digit() || next == '\'' || next == 'w' {
+                return Err(ParseError::new(
+                    pos,
+                    format!("unexpected character '{}'", next),
+                ));
+            }
+        }
+
+        moves.push(Move {
+            direction,
+            turn,
+            layers,
+        });
+    }
+
+    Ok(moves)
+}
+
+/// Simplifies a move sequence: moves are written in canonical form and
+/// consecutive turns of the same layers are merged (`R R` becomes `R2`) or
+/// cancelled (`R R'`), also across other moves on the same axis (`R L R'`
+/// becomes `L`).
+pub fn simplify(moves: &[Move]) -> Vec<Move> {
+    let mut simplified: Vec<Move> = vec![];
+
+    for m in moves {
+        let m = m.canonical();
+
+        // moves on the same axis commute, so look back through all of them
+        let mut merged = false;
+        for i in (0..simplified.len()).rev() {
+            if !simplified[i].same_axis(&m) {
+                break;
+            }
+            if simplified[i].direction == m.direction && simplified[i].layers == m.layers {
+                let quarter_turns = simplified[i].turn.quarter_turns() + m.turn.quarter_turns();
+                match Turn::from_quarter_turns(quarter_turns) {
+                    Some(turn) => simplified[i].turn = turn,
+                    None => {
+                        simplified.remove(i);
+                    }
+                }
+                merged = true;
+                break;
+            }
+        }
+
+        if !merged {
+            simplified.push(m);
+        }
+    }
+
+    simplified
+}
+
+impl FromStr for Scramble {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Scramble {
+            moves: parse_moves(s)?,
+        })
+    }
+}
@@ -0,0 +1,192 @@
+use crate::cube::Cube;
+use crate::notation::parse_moves;
+use crate::turns::{Layers, Move};
+use serde_json::{json, Value};
+
+/// A move of a reconstruction.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimedMove {
+    pub m: Move,
+    /// Seconds from the start of the solve, if known.
+    pub time: Option<f64>,
+    /// Label of the step the move is part of, e.g. "cross".
+    pub step: Option<String>,
+}
+
+/// Moves done to solve a scramble, grouped into labelled steps.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Reconstruction {
+    pub moves: Vec<TimedMove>,
+}
+
+/// Move count and duration of a step.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Split {
+    pub label: String,
+    pub moves: usize,
+    /// Seconds from the end of the previous step, if the moves are timed.
+    pub time: Option<f64>,
+}
+
+impl Reconstruction {
+    /// Parses a reconstruction written as text, one step per line.
+    ///
+    /// A `//` comment labels the step of its line, e.g. `y R' F R // cross`,
+    /// and a move can be followed by the time it was done at, e.g. `R@1.25`.
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut moves = vec![];
+        for (i, line) in text.lines().enumerate() {
+            let (notation, label) = match line.split_once("//") {
+                Some((notation, label)) => (notation, Some(label.trim().to_string())),
+                None => (line, None),
+            };
+            for token in notation.split_whitespace() {
+                let (token, time) = match token.split_once('@') {
+                    Some((token, time)) => {
+                        let time = time
+                            .parse::<f64>()
+                            .map_err(|_| format!("line {}: bad time in {}", i + 1, token))?;
+                        (token, Some(time))
+                    }
+                    None => (token, None),
+                };
+                for m in parse_moves(token).map_err(|e| format!("line {}: {}", i + 1, e))? {
+                    moves.push(TimedMove {
+                        m,
+                        time,
+                        step: label.clone().filter(|label| !label.is_empty()),
+                    });
+                }
+            }
+        }
+        Ok(Self { moves })
+    }
+
+    /// Writes the reconstruction as text that [`Reconstruction::parse`] reads
+    /// back, one step per line.
+    pub fn to_text(&self) -> String {
+        let lines: Vec<String> = self
+            .steps()
+            .into_iter()
+            .map(|step| {
+                let moves: Vec<String> = step
+                    .iter()
+                    .map(|timed| match timed.time {
+                        Some(time) => format!("{}@{}", timed.m, time),
+                        None => timed.m.to_string(),
+                    })
+                    .collect();
+                match &step[0].step {
+                    Some(label) => format!("{} // {}", moves.join(" "), label),
+                    None => moves.join(" "),
+                }
+            })
+            .collect();
+        lines.join("\n")
+    }
+
+    /// Runs of consecutive moves with the same label.
+    fn steps(&self) -> Vec<&[TimedMove]> {
+        let mut steps = vec![];
+        let mut start = 0;
+        for end in 1..=self.moves.len() {
+            if end == self.moves.len() || self.moves[end].step != self.moves[start].step {
+                steps.push(&self.moves[start..end]);
+                start = end;
+            }
+        }
+        steps
+    }
+
+    /// Reads a reconstruction stored with a solve: an array of
+    /// `{"move", "time", "step"}` objects, `time` and `step` being optional.
+    pub fn from_value(json: &Value) -> Option<Self> {
+        let mut moves = vec![];
+        for entry in json.as_array()? {
+            let m = parse_moves(entry["move"].as_str()?).ok()?;
+            moves.push(TimedMove {
+                m: *m.first()?,
+                time: entry["time"].as_f64(),
+                step: entry["step"].as_str().map(String::from),
+            });
+        }
+        Some(Self { moves })
+    }
+
+    pub fn to_value(&self) -> Value {
+        let moves: Vec<Value> = self
+            .moves
+            .iter()
+            .map(|timed| {
+                let mut entry = json!({ "move": timed.m.to_string() });
+                if let Some(time) = timed.time {
+                    entry["time"] = Value::from(time);
+                }
+                if let Some(step) = &timed.step {
+                    entry["step"] = Value::from(step.as_str());
+                }
+                entry
+            })
+            .collect();
+        Value::from(moves)
+    }
+
+    /// Checks that the reconstruction solves the scramble on a cube of `size`.
+    pub fn validate(&self, scramble: &[Move], size: u8) -> Result<(), String> {
+        let mut cube = Cube::scrambled(size, scramble);
+        for timed in &self.moves {
+            cube.apply(&timed.m);
+        }
+        match cube.is_solved() {
+            true => Ok(()),
+            false => Err("the reconstruction doesn't solve the scramble".to_string()),
+        }
+    }
+
+    /// Number of turns, not counting rotations.
+    pub fn move_count(&self) -> usize {
+        self.moves
+            .iter()
+            .filter(|timed| timed.m.layers != Layers::Rotation)
+            .count()
+    }
+
+    /// Turns per second over a solve of `time` seconds.
+    pub fn tps(&self, time: f64) -> f64 {
+        match time > 0.0 {
+            true => self.move_count() as f64 / time,
+            false => 0.0,
+        }
+    }
+
+    /// Move count and time of each step, unlabelled steps included with an
+    /// empty label.
+    pub fn splits(&self) -> Vec<Split> {
+        let mut step_start = Some(0.0);
+        self.steps()
+            .into_iter()
+            .map(|step| {
+                let end = step.last().unwrap().time;
+                let split = Split {
+                    label: step[0].step.clone().unwrap_or_default(),
+                    moves: step
+                        .iter()
+                        .filter(|timed| timed.m.layers != Layers::Rotation)
+                        .count(),
+                    time: end.zip(step_start).map(|(end, start)| end - start),
+                };
+                step_start = end;
+                split
+            })
+            .collect()
+    }
+
+    /// The scrambled cube after the first `count` moves.
+    pub fn cube_after(&self, scramble: &[Move], size: u8, count: usize) -> Cube {
+        let mut cube = Cube::scrambled(size, scramble);
+        for timed in self.moves.iter().take(count) {
+            cube.apply(&timed.m);
+        }
+        cube
+    }
+}
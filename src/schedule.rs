@@ -0,0 +1,109 @@
+use serde_json::{json, Value};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+// SM-2 spaced repetition. Each case has an ease factor and an interval in
+// days; recalling a case grows the interval by the ease, forgetting it starts
+// the case over at one day. The grade of a review (0-5) moves the ease up or
+// down, so cases that are slow or not recognised come back more often.
+
+const DAY: u64 = 24 * 60 * 60;
+
+/// Grade given when a case wasn't recognised.
+pub const FORGOTTEN: u8 = 1;
+
+/// Review schedule of an algorithm case.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Schedule {
+    pub ease: f64,
+    /// Days until the next review.
+    pub interval: u64,
+    /// Reviews in a row the case was recalled.
+    pub repetitions: u64,
+    /// When the case is next due, in unix seconds.
+    pub due: u64,
+}
+
+impl Default for Schedule {
+    fn default() -> Self {
+        Self {
+            ease: 2.5,
+            interval: 0,
+            repetitions: 0,
+            due: 0,
+        }
+    }
+}
+
+impl Schedule {
+    /// Schedule after a review with the given grade at `now`.
+    pub fn review(&self, grade: u8, now: u64) -> Self {
+        let grade = grade.min(5);
+        let (repetitions, interval) = match grade {
+            0..=2 => (0, 1),
+            _ => {
+                let interval = match self.repetitions {
+                    0 => 1,
+                    1 => 6,
+                    _ => (self.interval as f64 * self.ease).round() as u64,
+                };
+                (self.repetitions + 1, interval)
+            }
+        };
+        let miss = (5 - grade) as f64;
+        Self {
+            ease: (self.ease + 0.1 - miss * (0.08 + miss * 0.02)).max(1.3),
+            interval,
+            repetitions,
+            due: now + interval * DAY,
+        }
+    }
+
+    pub fn is_due(&self, now: u64) -> bool {
+        self.due <= now
+    }
+
+    pub fn from_value(json: &Value) -> Option<Self> {
+        Some(Self {
+            ease: json["ease"].as_f64()?,
+            interval: json["interval"].as_u64()?,
+            repetitions: json["repetitions"].as_u64()?,
+            due: json["due"].as_u64()?,
+        })
+    }
+
+    pub fn to_value(self) -> Value {
+        json!({
+            "ease": self.ease,
+            "interval": self.interval,
+            "repetitions": self.repetitions,
+            "due": self.due,
+        })
+    }
+
+    /// Time until the case is due, e.g. "3d" or "5h", "due" once it is.
+    pub fn due_in(&self, now: u64) -> String {
+        match self.due.saturating_sub(now) {
+            0 => "due".to_string(),
+            left if left >= DAY => format!("{}d", (left + DAY - 1) / DAY),
+            left => format!("{}h", (left + 60 * 60 - 1) / (60 * 60)),
+        }
+    }
+}
+
+/// Grade of a recognised case from its execution time: 5 if it was at least
+/// as fast as the case's average, 4 within half as slow again, 3 otherwise.
+pub fn grade(time: f64, average: Option<f64>) -> u8 {
+    match average {
+        None => 4,
+        Some(average) if time <= average => 5,
+        Some(average) if time <= average * 1.5 => 4,
+        Some(_) => 3,
+    }
+}
+
+/// Current time in unix seconds.
+pub fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |time| time.as_secs())
+}
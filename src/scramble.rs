@@ -1,46 +1,184 @@
-use crate::turns::Move;
+use crate::turns::{Direction, Layers, Move, Turn};
+use rand::Rng;
 use std::fmt::Display;
 
-#[derive(Debug)]
+/// Events a session can be scrambled for.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Variant {
+    ThreeByThree,
+    OneHanded,
+    Fmc,
+    ThreeBld,
+    FourBld,
+    FiveBld,
+}
+
+impl Variant {
+    pub const ALL: [Variant; 6] = [
+        Variant::ThreeByThree,
+        Variant::OneHanded,
+        Variant::Fmc,
+        Variant::ThreeBld,
+        Variant::FourBld,
+        Variant::FiveBld,
+    ];
+
+    /// WCA event id, used to store the variant in the session.
+    pub fn id(&self) -> &'static str {
+        match self {
+            Variant::ThreeByThree => "333",
+            Variant::OneHanded => "333oh",
+            Variant::Fmc => "333fm",
+            Variant::ThreeBld => "333bf",
+            Variant::FourBld => "444bf",
+            Variant::FiveBld => "555bf",
+        }
+    }
+
+    pub fn from_id(id: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|variant| variant.id() == id)
+    }
+
+    /// The variant after this one, wrapping around.
+    pub fn next(&self) -> Self {
+        let index = Self::ALL.iter().position(|v| v == self).unwrap();
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+
+    /// Number of layers of the puzzle the variant is solved on.
+    pub fn puzzle_size(&self) -> u8 {
+        match self {
+            Variant::FourBld => 4,
+            Variant::FiveBld => 5,
+            _ => 3,
+        }
+    }
+}
+
+impl Display for Variant {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Variant::ThreeByThree => write!(f, "3x3"),
+            Variant::OneHanded => write!(f, "3x3 OH"),
+            Variant::Fmc => write!(f, "3x3 FMC"),
+            Variant::ThreeBld => write!(f, "3BLD"),
+            Variant::FourBld => write!(f, "4BLD"),
+            Variant::FiveBld => write!(f, "5BLD"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
 pub struct Scramble {
     pub moves: Vec<Move>,
 }
 
 impl Scramble {
-    pub fn new(length: usize) -> Self {
-        let mut moves = vec![Move::random()];
-        let mut pos = 0;
-        while moves.len() < length {
-            let mut next_move = Move::random();
-            while next_move.direction == moves.last().unwrap().direction {
-                next_move = Move::random();
-            }
+    pub fn new<R: Rng + ?Sized>(length: usize, rng: &mut R) -> Self {
+        let mut moves = vec![];
+        extend(rng, &mut moves, length, &[Layers::Outer]);
+        Scramble { moves }
+    }
 
-            if pos >= 2 {
-                if moves.last().unwrap().direction.opposite() == next_move.direction {
-                    while moves[moves.len() - 2].direction == next_move.direction
-                        || next_move.direction == moves.last().unwrap().direction
-                    {
-                        next_move = Move::random();
+    /// Generates a scramble for the given event variant.
+    pub fn for_variant<R: Rng + ?Sized>(variant: Variant, rng: &mut R) -> Self {
+        match variant {
+            Variant::ThreeByThree | Variant::OneHanded => Scramble::new(20, rng),
+            Variant::Fmc => {
+                // WCA FMC scrambles start and end with R' U' F, so the padding
+                // can't cancel into the random part.
+                let padding = [
+                    Move::new(Direction::Right, Turn::CounterClockwise),
+                    Move::new(Direction::Up, Turn::CounterClockwise),
+                    Move::new(Direction::Front, Turn::Clockwise),
+                ];
+                let mut moves;
+                loop {
+                    moves = padding.to_vec();
+                    extend(rng, &mut moves, padding.len() + 20, &[Layers::Outer]);
+                    if valid_next(&moves, &padding[0]) {
+                        break;
                     }
                 }
+                moves.extend(padding);
+                Scramble { moves }
+            }
+            Variant::ThreeBld => {
+                let mut scramble = Scramble::new(20, rng);
+                scramble.moves.extend(orientation_suffix(rng, true));
+                scramble
             }
+            Variant::FourBld | Variant::FiveBld => {
+                let length = if variant == Variant::FourBld { 40 } else { 60 };
+                let mut moves = vec![];
+                extend(rng, &mut moves, length, &[Layers::Outer, Layers::Wide(2)]);
+                moves.extend(orientation_suffix(rng, false));
+                Scramble { moves }
+            }
+        }
+    }
+}
 
-            moves.push(next_move);
-            pos += 1;
+/// Whether `next` may follow `moves`: never the same face twice in a row, and
+/// never the same face either side of its opposite (e.g. `R L R`).
+fn valid_next(moves: &[Move], next: &Move) -> bool {
+    match moves {
+        [] => true,
+        [.., last] if last.direction == next.direction => false,
+        [.., second_last, last] => {
+            last.direction.opposite() != next.direction || second_last.direction != next.direction
         }
+        _ => true,
+    }
+}
 
-        Scramble { moves }
+/// Appends random moves until the scramble is `length` moves long.
+fn extend<R: Rng + ?Sized>(rng: &mut R, moves: &mut Vec<Move>, length: usize, layers: &[Layers]) {
+    while moves.len() < length {
+        let mut next_move = Move::random_layers(rng, layers);
+        while !valid_next(moves, &next_move) {
+            next_move = Move::random_layers(rng, layers);
+        }
+        moves.push(next_move);
     }
 }
 
+/// Random whole-cube reorientation for blindfolded scrambles, so memo can't
+/// rely on a fixed orientation. One of the 24 orientations is picked
+/// uniformly: a face is brought to the top, then the cube is turned about the
+/// vertical axis. 3BLD writes these as wide moves (`Rw Uw'`), bigger cubes as
+/// rotations (`x y2`).
+fn orientation_suffix<R: Rng + ?Sized>(rng: &mut R, wide: bool) -> Vec<Move> {
+    let top = match rng.gen_range(0..6) {
+        0 => None,
+        1 => Some((Direction::Right, Turn::Clockwise)),
+        2 => Some((Direction::Right, Turn::Double)),
+        3 => Some((Direction::Right, Turn::CounterClockwise)),
+        4 => Some((Direction::Front, Turn::Clockwise)),
+        5 => Some((Direction::Front, Turn::CounterClockwise)),
+        _ => panic!("Random number generator failed"),
+    };
+    let spin = match rng.gen_range(0..4) {
+        0 => None,
+        1 => Some((Direction::Up, Turn::Clockwise)),
+        2 => Some((Direction::Up, Turn::Double)),
+        3 => Some((Direction::Up, Turn::CounterClockwise)),
+        _ => panic!("Random number generator failed"),
+    };
+    [top, spin]
+        .into_iter()
+        .flatten()
+        .map(|(direction, turn)| match wide {
+            true => Move::wide(direction, turn, 2),
+            false => Move::rotation(direction, turn),
+        })
+        .collect()
+}
+
 impl Display for Scramble {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
-        for i in 0..self.moves.len() {
-            match write!(f, "{}{} ", self.moves[i].direction, self.moves[i].turn) {
-                Ok(_) => {}
-                Err(e) => return Err(e),
-            };
+        for m in &self.moves {
+            write!(f, "{} ", m)?;
         }
         Ok(())
     }
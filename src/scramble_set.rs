@@ -0,0 +1,102 @@
+use crate::app::AppResult;
+use crate::scramble::Scramble;
+use serde_json::Value;
+use std::fs;
+use std::path::Path;
+
+/// Pre-generated scrambles, handed out in order, so everyone in a club or
+/// team solves the same ones.
+#[derive(Debug)]
+pub struct ScrambleSet {
+    pub scrambles: Vec<Scramble>,
+    /// Number of scrambles handed out so far, one past the end once the set
+    /// is used up.
+    pub position: usize,
+}
+
+impl ScrambleSet {
+    /// Loads a scramble set from a file.
+    ///
+    /// JSON files hold either an array of scramble strings or an object with a
+    /// `scrambles` array. Anything else is read as text with one scramble per
+    /// line; blank lines, `#` comments and numbering like `1.` or `3)` are
+    /// skipped.
+    pub fn load(path: &Path) -> AppResult<Self> {
+        let contents = fs::read_to_string(path)
+            .map_err(|e| format!("couldn't read {}: {}", path.display(), e))?;
+
+        let scrambles = match serde_json::from_str::<Value>(&contents) {
+            Ok(json) => Self::from_json(&json)?,
+            Err(_) => Self::from_text(&contents)?,
+        };
+        if scrambles.is_empty() {
+            return Err(format!("no scrambles in {}", path.display()).into());
+        }
+
+        Ok(Self {
+            scrambles,
+            position: 0,
+        })
+    }
+
+    fn from_json(json: &Value) -> AppResult<Vec<Scramble>> {
+        let list = match json {
+            Value::Array(list) => list,
+            _ => json["scrambles"]
+                .as_array()
+                .ok_or("expected an array of scrambles")?,
+        };
+
+        let mut scrambles = vec![];
+        for (i, scramble) in list.iter().enumerate() {
+            let scramble = scramble
+                .as_str()
+                .ok_or(format!("scramble {} is not a string", i + 1))?;
+            scrambles.push(
+                scramble
+                    .parse()
+                    .map_err(|e| format!("scramble {}: {}", i + 1, e))?,
+            );
+        }
+        Ok(scrambles)
+    }
+
+    fn from_text(contents: &str) -> AppResult<Vec<Scramble>> {
+        let mut scrambles = vec![];
+        for (i, line) in contents.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            // strip numbering, e.g. "1. R U ..." or "2) R U ..."
+            let line = match line.split_once(['.', ')']) {
+                Some((number, rest)) if number.trim().parse::<usize>().is_ok() => rest,
+                _ => line,
+            };
+            scrambles.push(line.parse().map_err(|e| format!("line {}: {}", i + 1, e))?);
+        }
+        Ok(scrambles)
+    }
+
+    /// Takes the next scramble of the set, `None` once it is used up.
+    pub fn take_next(&mut self) -> Option<Scramble> {
+        self.position = (self.position + 1).min(self.scrambles.len() + 1);
+        self.scrambles.get(self.position - 1).cloned()
+    }
+
+    pub fn len(&self) -> usize {
+        self.scrambles.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.scrambles.is_empty()
+    }
+
+    /// Position of the last scramble handed out, e.g. "3/12".
+    pub fn progress(&self) -> String {
+        match self.position > self.scrambles.len() {
+            true => "done".to_string(),
+            false => format!("{}/{}", self.position, self.scrambles.len()),
+        }
+    }
+}
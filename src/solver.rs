@@ -0,0 +1,422 @@
+use crate::cubie::{index_move, move_cube, CubieCube};
+use crate::turns::Move;
+use std::collections::VecDeque;
+use std::sync::{Once, OnceLock};
+use std::thread;
+use std::time::{Duration, Instant};
+
+// Two-phase solver (Kociemba). Phase 1 brings the cube into the subgroup
+// <U, D, R2, F2, L2, B2>, where all pieces are oriented and the E-slice edges
+// are in the E slice; phase 2 solves it using only those moves. Each phase is
+// an IDA* search over small coordinates, with pruning tables built once on
+// first use.
+
+/// Time a scramble's solution is searched for before giving up.
+pub const BUDGET: Duration = Duration::from_secs(1);
+
+const N_TWIST: usize = 2187;
+const N_FLIP: usize = 2048;
+const N_SLICE: usize = 495;
+const N_PERM: usize = 40320;
+const N_SLICE_SORTED: usize = 24;
+const N_MOVES: usize = 18;
+
+/// Move indices allowed in phase 2: U, U2, U', R2, F2, D, D2, D', L2, B2.
+const PHASE2_MOVES: [usize; 10] = [0, 1, 2, 4, 7, 9, 10, 11, 13, 16];
+
+fn binomial(n: usize, k: usize) -> usize {
+    if k > n {
+        return 0;
+    }
+    let mut result = 1;
+    for i in 0..k {
+        result = result * (n - i) / (i + 1);
+    }
+    result
+}
+
+fn rotate_left(perm: &mut [u8], right: usize) {
+    perm[..=right].rotate_left(1);
+}
+
+fn rotate_right(perm: &mut [u8], right: usize) {
+    perm[..=right].rotate_right(1);
+}
+
+/// Rank of a permutation of `0..perm.len()`.
+fn perm_coord(perm: &[u8]) -> usize {
+    let mut perm = perm.to_vec();
+    let mut coord = 0;
+    for j in (1..perm.len()).rev() {
+        let mut k = 0;
+        while perm[j] as usize != j {
+            rotate_left(&mut perm, j);
+            k += 1;
+        }
+        coord = (j + 1) * coord + k;
+    }
+    coord
+}
+
+/// Permutation of `0..len` with the given rank.
+fn set_perm_coord(mut coord: usize, len: usize) -> Vec<u8> {
+    let mut perm: Vec<u8> = (0..len as u8).collect();
+    for j in 0..len {
+        let mut k = coord % (j + 1);
+        coord /= j + 1;
+        while k > 0 {
+            rotate_right(&mut perm, j);
+            k -= 1;
+        }
+    }
+    perm
+}
+
+impl CubieCube {
+    fn twist(&self) -> usize {
+        self.co[..7].iter().fold(0, |t, &c| 3 * t + c as usize)
+    }
+
+    fn set_twist(&mut self, mut twist: usize) {
+        let mut sum = 0;
+        for i in (0..7).rev() {
+            self.co[i] = (twist % 3) as u8;
+            sum += self.co[i];
+            twist /= 3;
+        }
+        self.co[7] = (3 - sum % 3) % 3;
+    }
+
+    fn flip(&self) -> usize {
+        self.eo[..11].iter().fold(0, |f, &e| 2 * f + e as usize)
+    }
+
+    fn set_flip(&mut self, mut flip: usize) {
+        let mut sum = 0;
+        for i in (0..11).rev() {
+            self.eo[i] = (flip % 2) as u8;
+            sum += self.eo[i];
+            flip /= 2;
+        }
+        self.eo[11] = sum % 2;
+    }
+
+    /// Positions of the four E-slice edges, ignoring their order.
+    fn slice(&self) -> usize {
+        let mut coord = 0;
+        let mut seen = 0;
+        for j in (0..12).rev() {
+            if self.ep[j] >= 8 {
+                coord += binomial(11 - j, seen + 1);
+                seen += 1;
+            }
+        }
+        coord
+    }
+
+    fn set_slice(&mut self, mut coord: usize) {
+        let mut ep = [u8::MAX; 12];
+        let mut left = 4;
+        for (j, edge) in ep.iter_mut().enumerate() {
+            if left > 0 && coord >= binomial(11 - j, left) {
+                *edge = 8 + (4 - left) as u8;
+                coord -= binomial(11 - j, left);
+                left -= 1;
+            }
+        }
+        let mut other = 0;
+        for edge in ep.iter_mut() {
+            if *edge == u8::MAX {
+                *edge = other;
+                other += 1;
+            }
+        }
+        self.ep = ep;
+    }
+
+    fn corners(&self) -> usize {
+        perm_coord(&self.cp)
+    }
+
+    fn set_corners(&mut self, coord: usize) {
+        self.cp.copy_from_slice(&set_perm_coord(coord, 8));
+    }
+
+    /// Permutation of the U and D layer edges, only meaningful in phase 2.
+    fn ud_edges(&self) -> usize {
+        perm_coord(&self.ep[..8])
+    }
+
+    fn set_ud_edges(&mut self, coord: usize) {
+        self.ep[..8].copy_from_slice(&set_perm_coord(coord, 8));
+    }
+
+    /// Permutation of the E-slice edges, only meaningful in phase 2.
+    fn slice_sorted(&self) -> usize {
+        let perm: Vec<u8> = self.ep[8..].iter().map(|e| e - 8).collect();
+        perm_coord(&perm)
+    }
+
+    fn set_slice_sorted(&mut self, coord: usize) {
+        for (i, e) in set_perm_coord(coord, 4).into_iter().enumerate() {
+            self.ep[8 + i] = e + 8;
+        }
+    }
+}
+
+struct Tables {
+    twist_move: Vec<[u16; N_MOVES]>,
+    flip_move: Vec<[u16; N_MOVES]>,
+    slice_move: Vec<[u16; N_MOVES]>,
+    corners_move: Vec<[u16; N_MOVES]>,
+    ud_edges_move: Vec<[u16; N_MOVES]>,
+    slice_sorted_move: Vec<[u16; N_MOVES]>,
+    twist_slice_prune: Vec<u8>,
+    flip_slice_prune: Vec<u8>,
+    corners_slice_prune: Vec<u8>,
+    edges_slice_prune: Vec<u8>,
+}
+
+/// Move table of a coordinate, for the given moves (other entries stay 0).
+fn move_table(
+    size: usize,
+    moves: &[usize],
+    set: impl Fn(&mut CubieCube, usize),
+    get: impl Fn(&CubieCube) -> usize,
+) -> Vec<[u16; N_MOVES]> {
+    let move_cubes: Vec<CubieCube> = (0..N_MOVES).map(move_cube).collect();
+    let mut table = vec![[0; N_MOVES]; size];
+    for (coord, row) in table.iter_mut().enumerate() {
+        let mut cube = CubieCube::SOLVED;
+        set(&mut cube, coord);
+        for &m in moves {
+            row[m] = get(&cube.multiply(&move_cubes[m])) as u16;
+        }
+    }
+    table
+}
+
+/// Distance to solved of every pair of coordinates `(a, b)`, stored at
+/// `a * b_size + b`.
+fn prune_table(a_move: &[[u16; N_MOVES]], b_move: &[[u16; N_MOVES]], moves: &[usize]) -> Vec<u8> {
+    let b_size = b_move.len();
+    let mut table = vec![u8::MAX; a_move.len() * b_size];
+    let mut queue = VecDeque::new();
+    table[0] = 0;
+    queue.push_back(0);
+    while let Some(index) = queue.pop_front() {
+        let (a, b) = (index / b_size, index % b_size);
+        for &m in moves {
+            let next = a_move[a][m] as usize * b_size + b_move[b][m] as usize;
+            if table[next] == u8::MAX {
+                table[next] = table[index] + 1;
+                queue.push_back(next);
+            }
+        }
+    }
+    table
+}
+
+static TABLES: OnceLock<Tables> = OnceLock::new();
+
+fn tables() -> &'static Tables {
+    TABLES.get_or_init(|| {
+        let all: Vec<usize> = (0..N_MOVES).collect();
+        let twist_move = move_table(N_TWIST, &all, CubieCube::set_twist, CubieCube::twist);
+        let flip_move = move_table(N_FLIP, &all, CubieCube::set_flip, CubieCube::flip);
+        let slice_move = move_table(N_SLICE, &all, CubieCube::set_slice, CubieCube::slice);
+        let corners_move = move_table(
+            N_PERM,
+            &PHASE2_MOVES,
+            CubieCube::set_corners,
+            CubieCube::corners,
+        );
+        let ud_edges_move = move_table(
+            N_PERM,
+            &PHASE2_MOVES,
+            CubieCube::set_ud_edges,
+            CubieCube::ud_edges,
+        );
+        let slice_sorted_move = move_table(
+            N_SLICE_SORTED,
+            &PHASE2_MOVES,
+            CubieCube::set_slice_sorted,
+            CubieCube::slice_sorted,
+        );
+        Tables {
+            twist_slice_prune: prune_table(&twist_move, &slice_move, &all),
+            flip_slice_prune: prune_table(&flip_move, &slice_move, &all),
+            corners_slice_prune: prune_table(&corners_move, &slice_sorted_move, &PHASE2_MOVES),
+            edges_slice_prune: prune_table(&ud_edges_move, &slice_sorted_move, &PHASE2_MOVES),
+            twist_move,
+            flip_move,
+            slice_move,
+            corners_move,
+            ud_edges_move,
+            slice_sorted_move,
+        }
+    })
+}
+
+/// Builds the tables on a background thread, as they take a moment and the
+/// first solve would otherwise wait for them. Does nothing after the first call.
+pub fn prepare() {
+    static STARTED: Once = Once::new();
+    STARTED.call_once(|| {
+        thread::spawn(tables);
+    });
+}
+
+/// Whether the tables are built, so solving won't wait for them.
+pub fn ready() -> bool {
+    TABLES.get().is_some()
+}
+
+/// Whether `m` may follow `previous`: never the same face twice, and
+/// opposite faces only in one order (U D, not D U).
+pub(crate) fn allowed_after(previous: Option<usize>, m: usize) -> bool {
+    match previous {
+        None => true,
+        Some(previous) => {
+            let (face, previous) = (m / 3, previous / 3);
+            face != previous && face + 3 != previous
+        }
+    }
+}
+
+struct Search<'a> {
+    tables: &'a Tables,
+    cube: CubieCube,
+    path: Vec<usize>,
+    max_length: usize,
+    deadline: Instant,
+    nodes: usize,
+    timed_out: bool,
+}
+
+impl<'a> Search<'a> {
+    /// Counts a node searched, giving up once the budget is spent.
+    fn out_of_time(&mut self) -> bool {
+        self.nodes += 1;
+        if self.nodes % 4096 == 0 && Instant::now() > self.deadline {
+            self.timed_out = true;
+        }
+        self.timed_out
+    }
+
+    fn phase1(&mut self, twist: usize, flip: usize, slice: usize, depth: usize) -> bool {
+        let t = self.tables;
+        if depth == 0 {
+            if twist != 0 || flip != 0 || slice != 0 {
+                return false;
+            }
+            // a phase 1 solution ending in a phase 2 move was already tried
+            // one move shorter
+            if let Some(&last) = self.path.last() {
+                if PHASE2_MOVES.contains(&last) {
+                    return false;
+                }
+            }
+            return self.start_phase2();
+        }
+
+        let distance = t.twist_slice_prune[twist * N_SLICE + slice]
+            .max(t.flip_slice_prune[flip * N_SLICE + slice]) as usize;
+        if distance > depth || self.out_of_time() {
+            return false;
+        }
+
+        for m in 0..N_MOVES {
+            if !allowed_after(self.path.last().copied(), m) {
+                continue;
+            }
+            self.path.push(m);
+            if self.phase1(
+                t.twist_move[twist][m] as usize,
+                t.flip_move[flip][m] as usize,
+                t.slice_move[slice][m] as usize,
+                depth - 1,
+            ) {
+                return true;
+            }
+            self.path.pop();
+        }
+        false
+    }
+
+    fn start_phase2(&mut self) -> bool {
+        let mut cube = self.cube;
+        for &m in &self.path {
+            cube.apply_index(m);
+        }
+        let (corners, edges, slice) = (cube.corners(), cube.ud_edges(), cube.slice_sorted());
+        let phase1_length = self.path.len();
+        for depth in 0..=self.max_length.saturating_sub(phase1_length) {
+            if self.phase2(corners, edges, slice, depth) {
+                return true;
+            }
+        }
+        false
+    }
+
+    fn phase2(&mut self, corners: usize, edges: usize, slice: usize, depth: usize) -> bool {
+        let t = self.tables;
+        let distance = t.corners_slice_prune[corners * N_SLICE_SORTED + slice]
+            .max(t.edges_slice_prune[edges * N_SLICE_SORTED + slice])
+            as usize;
+        if distance > depth || self.out_of_time() {
+            return false;
+        }
+        if depth == 0 {
+            return true;
+        }
+
+        for m in PHASE2_MOVES {
+            if !allowed_after(self.path.last().copied(), m) {
+                continue;
+            }
+            self.path.push(m);
+            if self.phase2(
+                t.corners_move[corners][m] as usize,
+                t.ud_edges_move[edges][m] as usize,
+                t.slice_sorted_move[slice][m] as usize,
+                depth - 1,
+            ) {
+                return true;
+            }
+            self.path.pop();
+        }
+        false
+    }
+}
+
+/// Finds face turns of at most `max_length` moves that solve `cube`.
+///
+/// The solution is usually not optimal but is found quickly for lengths of
+/// 22 and up. Returns `None` for unsolvable states, or if no solution is short
+/// enough or found within `budget`. Waits for the tables if they aren't built.
+pub fn solve(cube: &CubieCube, max_length: usize, budget: Duration) -> Option<Vec<Move>> {
+    if !cube.is_solvable() {
+        return None;
+    }
+    let tables = tables();
+    let mut search = Search {
+        tables,
+        cube: *cube,
+        path: vec![],
+        max_length,
+        deadline: Instant::now() + budget,
+        nodes: 0,
+        timed_out: false,
+    };
+    let (twist, flip, slice) = (cube.twist(), cube.flip(), cube.slice());
+    for depth in 0..=max_length {
+        if search.phase1(twist, flip, slice, depth) {
+            return Some(search.path.iter().map(|&m| index_move(m)).collect());
+        }
+        if search.timed_out {
+            return None;
+        }
+    }
+    None
+}
@@ -0,0 +1,228 @@
+use crate::cubie::*;
+use crate::scramble::Scramble;
+use crate::solver;
+use crate::turns::{Direction, Move, Turn};
+use rand::Rng;
+use std::fmt::Display;
+use std::time::Instant;
+
+// Subsets are generated as random states of the cube with some pieces
+// solved, then solved with the two-phase solver; the scramble is the inverse
+// of the solution. Scrambles are applied in WCA orientation (white top, green
+// front) and the cross is built on white, so the last layer is the yellow D
+// layer and the F2L slots are the U corners and E-slice edges.
+
+const LL_CORNERS: [usize; 4] = [DFR, DLF, DBL, DRB];
+const LL_EDGES: [usize; 4] = [DR, DF, DL, DB];
+const F2L_CORNERS: [usize; 4] = [URF, UFL, ULB, UBR];
+const F2L_EDGES: [usize; 4] = [FR, FL, BL, BR];
+
+/// Maximum length of a subset scramble.
+const MAX_LENGTH: usize = 24;
+
+/// Subsets of cube states to drill instead of full scrambles.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Subset {
+    /// Regular scrambles.
+    None,
+    /// Random last layer, so any OLL case.
+    Oll,
+    /// Oriented last layer, so any PLL case.
+    Pll,
+    /// Last layer with oriented edges, drilled by corner case.
+    Coll,
+    /// Last layer with oriented edges, drilled by full case.
+    Zbll,
+    /// Last F2L pair and last layer.
+    LastSlot,
+    /// Cross and last layer solved, F2L pairs scrambled.
+    F2l,
+    /// Cross solved, everything else scrambled.
+    CrossSolved,
+    /// All edges oriented, everything else scrambled.
+    EoSolved,
+    /// Only R and U turns.
+    TwoGen,
+}
+
+impl Subset {
+    pub const ALL: [Subset; 10] = [
+        Subset::None,
+        Subset::Oll,
+        Subset::Pll,
+        Subset::Coll,
+        Subset::Zbll,
+        Subset::LastSlot,
+        Subset::F2l,
+        Subset::CrossSolved,
+        Subset::EoSolved,
+        Subset::TwoGen,
+    ];
+
+    /// Id used to store the subset in the session.
+    pub fn id(&self) -> &'static str {
+        match self {
+            Subset::None => "none",
+            Subset::Oll => "oll",
+            Subset::Pll => "pll",
+            Subset::Coll => "coll",
+            Subset::Zbll => "zbll",
+            Subset::LastSlot => "lsll",
+            Subset::F2l => "f2l",
+            Subset::CrossSolved => "cross",
+            Subset::EoSolved => "eo",
+            Subset::TwoGen => "2gen",
+        }
+    }
+
+    pub fn from_id(id: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|subset| subset.id() == id)
+    }
+
+    /// The subset after this one, wrapping around.
+    pub fn next(&self) -> Self {
+        let index = Self::ALL.iter().position(|s| s == self).unwrap();
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+
+    /// A random state of the subset.
+    fn random_state<R: Rng + ?Sized>(&self, rng: &mut R) -> CubieCube {
+        let all_corners: Vec<usize> = (0..8).collect();
+        let all_edges: Vec<usize> = (0..12).collect();
+        let last_slot_corners = [URF, DFR, DLF, DBL, DRB];
+        let last_slot_edges = [FR, DR, DF, DL, DB];
+        let not_cross = [DR, DF, DL, DB, FR, FL, BL, BR];
+        match self {
+            Subset::Oll => CubieCube::random_with(rng, &LL_CORNERS, true, &LL_EDGES, true),
+            Subset::Pll => CubieCube::random_with(rng, &LL_CORNERS, false, &LL_EDGES, false),
+            Subset::Coll | Subset::Zbll => {
+                CubieCube::random_with(rng, &LL_CORNERS, true, &LL_EDGES, false)
+            }
+            Subset::LastSlot => {
+                CubieCube::random_with(rng, &last_slot_corners, true, &last_slot_edges, true)
+            }
+            Subset::F2l => CubieCube::random_with(rng, &F2L_CORNERS, true, &F2L_EDGES, true),
+            Subset::CrossSolved => {
+                CubieCube::random_with(rng, &all_corners, true, &not_cross, true)
+            }
+            Subset::EoSolved => CubieCube::random_with(rng, &all_corners, true, &all_edges, false),
+            Subset::None | Subset::TwoGen => {
+                CubieCube::random_with(rng, &all_corners, true, &all_edges, true)
+            }
+        }
+    }
+
+    /// Generates a scramble that reaches a random state of the subset, along
+    /// with the case it sets up for last layer subsets. Returns `None` if the
+    /// solver doesn't find one within its budget.
+    pub fn scramble<R: Rng + ?Sized>(&self, rng: &mut R) -> Option<(Scramble, Option<String>)> {
+        if *self == Subset::TwoGen {
+            return Some((two_gen(rng, 25), None));
+        }
+
+        let state = self.random_state(rng);
+        let start = Instant::now();
+        let solution = solver::solve(&state, MAX_LENGTH, solver::BUDGET).or_else(|| {
+            solver::solve(&state, 30, solver::BUDGET.saturating_sub(start.elapsed()))
+        })?;
+        let scramble = Scramble {
+            moves: invert(&solution),
+        };
+        Some((scramble, self.case(&state)))
+    }
+
+    /// Name of the last layer case of a state, the same for every AUF of it.
+    ///
+    /// Cases are written as the orientation and/or permutation of the D layer
+    /// pieces, e.g. `CO 0012 EO 0101`, in the rotation that sorts first.
+    pub fn case(&self, state: &CubieCube) -> Option<String> {
+        let (corners, edges, orientation, permutation) = match self {
+            Subset::Oll => (true, true, true, false),
+            Subset::Pll => (true, true, false, true),
+            Subset::Coll => (true, false, true, true),
+            Subset::Zbll => (true, true, true, true),
+            _ => return None,
+        };
+
+        let d = move_cube(3 * face_index(Direction::Down));
+        let mut best: Option<String> = None;
+        let mut pre = CubieCube::SOLVED;
+        for _ in 0..4 {
+            let mut cube = pre.multiply(state);
+            for _ in 0..4 {
+                let mut parts = vec![];
+                let relative = |piece: u8| (piece - 4).to_string();
+                if corners && orientation {
+                    parts.push(format!("CO {}", digits(LL_CORNERS.map(|c| cube.co[c]))));
+                }
+                if corners && permutation {
+                    parts.push(format!(
+                        "CP {}",
+                        concat(LL_CORNERS.map(|c| relative(cube.cp[c])))
+                    ));
+                }
+                if edges && orientation {
+                    parts.push(format!("EO {}", digits(LL_EDGES.map(|e| cube.eo[e]))));
+                }
+                if edges && permutation {
+                    parts.push(format!(
+                        "EP {}",
+                        concat(LL_EDGES.map(|e| relative(cube.ep[e])))
+                    ));
+                }
+                let case = parts.join(" ");
+                if best.as_ref().map_or(true, |best| case < *best) {
+                    best = Some(case);
+                }
+                cube = cube.multiply(&d);
+            }
+            pre = pre.multiply(&d);
+        }
+        best
+    }
+}
+
+fn digits(values: [u8; 4]) -> String {
+    values.iter().map(|v| v.to_string()).collect()
+}
+
+fn concat(values: [String; 4]) -> String {
+    values.concat()
+}
+
+/// Random move scramble using only R and U.
+fn two_gen<R: Rng + ?Sized>(rng: &mut R, length: usize) -> Scramble {
+    let mut moves: Vec<Move> = vec![];
+    while moves.len() < length {
+        let direction = match (moves.last(), rng.gen_bool(0.5)) {
+            (Some(last), _) if last.direction == Direction::Right => Direction::Up,
+            (Some(_), _) => Direction::Right,
+            (None, true) => Direction::Right,
+            (None, false) => Direction::Up,
+        };
+        let turn = match rng.gen_range(0..3) {
+            0 => Turn::Clockwise,
+            1 => Turn::CounterClockwise,
+            _ => Turn::Double,
+        };
+        moves.push(Move::new(direction, turn));
+    }
+    Scramble { moves }
+}
+
+impl Display for Subset {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Subset::None => write!(f, "Full"),
+            Subset::Oll => write!(f, "OLL"),
+            Subset::Pll => write!(f, "PLL"),
+            Subset::Coll => write!(f, "COLL"),
+            Subset::Zbll => write!(f, "ZBLL"),
+            Subset::LastSlot => write!(f, "LS"),
+            Subset::F2l => write!(f, "F2L"),
+            Subset::CrossSolved => write!(f, "Cross solved"),
+            Subset::EoSolved => write!(f, "EO solved"),
+            Subset::TwoGen => write!(f, "2-gen <R,U>"),
+        }
+    }
+}
@@ -1,3 +1,5 @@
+use crate::clock::{Clock, SystemClock};
+use std::sync::Arc;
 use std::time::{Duration, Instant};
 
 #[derive(Debug)]
@@ -5,26 +7,62 @@ pub struct Timer {
     pub running: bool,
     pub time: Duration,
     pub now: Instant,
+    pub splits: Vec<Duration>, // time of each split since the start
+    pub clock: Arc<dyn Clock>, // where the time comes from
+}
+
+impl Default for Timer {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl Timer {
     pub fn new() -> Self {
+        Self::with_clock(Arc::new(SystemClock))
+    }
+
+    pub fn with_clock(clock: Arc<dyn Clock>) -> Self {
         Timer {
             time: Duration::new(0, 0),
             running: false,
-            now: Instant::now(),
+            now: clock.now(),
+            splits: vec![],
+            clock,
         }
     }
 
     pub fn start(&mut self) {
-        self.now = Instant::now();
+        self.start_at(self.clock.now());
+    }
+
+    /// Starts timing from `at`, e.g. when the key starting it was read.
+    pub fn start_at(&mut self, at: Instant) {
+        self.now = at;
         self.running = true;
+        self.splits.clear();
+    }
+
+    pub fn split(&mut self) -> Duration {
+        self.split_at(self.clock.now())
+    }
+
+    pub fn split_at(&mut self, at: Instant) -> Duration {
+        // record the time so far without stopping
+        let time = at.saturating_duration_since(self.now);
+        self.splits.push(time);
+        time
     }
 
     pub fn stop(&mut self) -> Duration {
+        self.stop_at(self.clock.now())
+    }
+
+    /// Stops timing at `at`, e.g. when the key stopping it was read.
+    pub fn stop_at(&mut self, at: Instant) -> Duration {
         self.running = false;
-        self.time = self.now.elapsed();
-        return self.time;
+        self.time = at.saturating_duration_since(self.now);
+        self.time
     }
 
     pub fn reset(&mut self) {
@@ -32,10 +70,15 @@ impl Timer {
     }
 
     pub fn get_time(&mut self) -> Duration {
+        self.time_at(self.clock.now())
+    }
+
+    /// The time at `at`, which is the stopped time once stopped.
+    pub fn time_at(&self, at: Instant) -> Duration {
         if self.running {
-            return self.now.elapsed();
+            at.saturating_duration_since(self.now)
         } else {
-            return self.time;
+            self.time
         }
     }
 }
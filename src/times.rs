@@ -5,6 +5,41 @@ use std::io::{Read, Write};
 use std::path::PathBuf;
 
 use crate::app::Penalty;
+use crate::inspection::Inspection;
+use crate::mbld::MbldResult;
+use crate::reconstruction::Reconstruction;
+use crate::schedule::Schedule;
+use crate::scramble::{Scramble, Variant};
+use crate::subset::Subset;
+
+/// Results of an algorithm trainer case.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct CaseStats {
+    pub count: usize,
+    pub mean: f64,
+    pub best: f64,
+    /// Attempts where the case wasn't recognised.
+    pub misses: usize,
+}
+
+// Times file of a new session
+const BASE_JSON: &str = r#"{
+    "bests": {
+        "single": {
+            "time": 0,
+            "scramble": ""
+        },
+        "ao5": {
+            "time": 0,
+            "times": []
+        },
+        "ao12": {
+            "time": 0,
+            "times": []
+        }
+    },
+    "times": []
+}"#;
 
 #[derive(Debug)]
 pub struct Times {
@@ -12,6 +47,12 @@ pub struct Times {
     pub times: Value,
 }
 
+impl Default for Times {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Times {
     pub fn new() -> Self {
         // get home directory
@@ -28,27 +69,8 @@ impl Times {
             std::fs::create_dir_all(path.parent().unwrap()).unwrap();
             let mut file = File::create(&path).unwrap();
 
-            // create the base json
-            let json = r#"{
-                "bests": {
-                    "single": {
-                        "time": 0,
-                        "scramble": ""
-                    },
-                    "ao5": {
-                        "time": 0,
-                        "times": []
-                    },
-                    "ao12": {
-                        "time": 0,
-                        "times": []
-                    }
-                },
-                "times": []
-            }"#;
-
             // write the json to the file
-            file.write_all(json.as_bytes()).unwrap();
+            file.write_all(BASE_JSON.as_bytes()).unwrap();
 
             // close the file
             drop(file);
@@ -71,7 +93,18 @@ impl Times {
         }
     }
 
+    pub fn in_memory() -> Self {
+        // empty session that is never saved, for tests
+        Self {
+            file_path: PathBuf::new(),
+            times: serde_json::from_str(BASE_JSON).unwrap(),
+        }
+    }
+
     pub fn save_to_file(&self) {
+        if self.file_path.as_os_str().is_empty() {
+            return;
+        }
         // write times to the file name, overrwrite old file
         let mut file = File::create(&self.file_path).unwrap();
 
@@ -85,8 +118,8 @@ impl Times {
         if index >= self.num_times() {
             return;
         }
-        let deleted_time = self.times["times"].as_array_mut().unwrap().remove(index);
-        self.update_after_delete(deleted_time["time"].as_f64().unwrap());
+        self.times["times"].as_array_mut().unwrap().remove(index);
+        self.update_after_delete();
     }
 
     pub fn del_last(&mut self) {
@@ -100,10 +133,16 @@ impl Times {
     pub fn add_time(&mut self, time: f64, scramble: String, penalty: Penalty) {
         // add time to times
 
+        // a +2 is stored in the time, as when it is toggled on later
+        let time = match penalty {
+            Penalty::PlusTwo => time + 2.0,
+            _ => time,
+        };
         let object = json!({
             "time": time,
             "scramble": scramble,
             "penalty": penalty.to_string(),
+            "event": self.variant().id(),
         });
 
         self.times["times"].as_array_mut().unwrap().push(object);
@@ -115,154 +154,25 @@ impl Times {
         self.times["times"].as_array().unwrap().len()
     }
 
-    pub fn update_after_delete(&mut self, time: f64) {
-        if self.num_times() == 0 {
-            // if there are no times, clear all best times
-            self.times["bests"]["single"]["time"] = Value::from(0.0);
-            self.times["bests"]["single"]["scramble"] = Value::from("");
-            self.times["bests"]["ao5"]["time"] = Value::from(0.0);
-            self.times["bests"]["ao5"]["times"]
-                .as_array_mut()
-                .unwrap()
-                .clear();
-            self.times["bests"]["ao12"]["time"] = Value::from(0.0);
-            self.times["bests"]["ao12"]["times"]
-                .as_array_mut()
-                .unwrap()
-                .clear();
-        }
-
-        if time == self.times["bests"]["single"]["time"].as_f64().unwrap() {
-            // if the deleted time was the best single, update best single
-            let mut best_single_time = -1.0;
-            let mut best_single_scramble = self.times["bests"]["single"]["scramble"].to_string();
-
-            for time in self.times["times"].as_array().unwrap() {
-                if best_single_time == -1.0 || time["time"].as_f64().unwrap() < best_single_time {
-                    best_single_time = time["time"].as_f64().unwrap();
-                    best_single_scramble = time["scramble"].as_str().unwrap().to_string();
-                }
-            }
-
-            self.times["bests"]["single"]["time"] = Value::from(best_single_time);
-            self.times["bests"]["single"]["scramble"] = Value::from(best_single_scramble);
-        }
-
-        // if the deleted time was the best ao5, update best ao5
-        // recalculate all ao5s, and find the best one
-        let mut best_ao5_time = -1.0;
-        let mut best_ao5_times: Vec<Value> = vec![];
-        for i in 0..self.num_times() {
-            self.times["times"][i]["ao5"] = Value::from(self.calc_ao5(i));
-            if i > 4
-                && (best_ao5_time == -1.0
-                    || self.times["times"][i]["ao5"].as_f64().unwrap() < best_ao5_time)
-            {
-                best_ao5_time = self.times["times"][i]["ao5"].as_f64().unwrap();
-                best_ao5_times = vec![
-                    self.times["times"][i - 4].clone(),
-                    self.times["times"][i - 3].clone(),
-                    self.times["times"][i - 2].clone(),
-                    self.times["times"][i - 1].clone(),
-                    self.times["times"][i].clone(),
-                ];
-            }
-        }
-        self.times["bests"]["ao5"]["time"] = Value::from(best_ao5_time);
-        self.times["bests"]["ao5"]["times"] = Value::from(best_ao5_times);
-
-        // update ao12
-        let mut best_ao12_time = -1.0;
-        let mut best_ao12_times: Vec<Value> = vec![];
-        for i in 0..self.num_times() {
-            self.times["times"][i]["ao12"] = Value::from(self.calc_ao12(i));
-            if i > 11
-                && (best_ao12_time == -1.0
-                    || self.times["times"][i]["ao12"].as_f64().unwrap() < best_ao12_time)
-            {
-                best_ao12_time = self.times["times"][i]["ao12"].as_f64().unwrap();
-                best_ao12_times = vec![
-                    self.times["times"][i - 11].clone(),
-                    self.times["times"][i - 10].clone(),
-                    self.times["times"][i - 9].clone(),
-                    self.times["times"][i - 8].clone(),
-                    self.times["times"][i - 7].clone(),
-                    self.times["times"][i - 6].clone(),
-                    self.times["times"][i - 5].clone(),
-                    self.times["times"][i - 4].clone(),
-                    self.times["times"][i - 3].clone(),
-                    self.times["times"][i - 2].clone(),
-                    self.times["times"][i - 1].clone(),
-                    self.times["times"][i].clone(),
-                ];
-            }
-        }
-
-        self.times["bests"]["ao12"]["time"] = Value::from(best_ao12_time);
-        self.times["bests"]["ao12"]["times"] = Value::from(best_ao12_times);
+    pub fn update_after_delete(&mut self) {
+        // averages including the deleted time are now of other times, and any
+        // best might have been the deleted time or in one of them
+        self.recalculate_averages();
+        self.recompute_bests();
     }
 
     pub fn update_after_add(&mut self) {
-        // SINGLES
-        // check if the new time is the best single
-        if (self.num_times() == 1)
-            || (self.times["times"][self.num_times() - 1]["time"]
-                .as_f64()
-                .unwrap()
-                < self.times["bests"]["single"]["time"].as_f64().unwrap())
-        {
-            self.times["bests"]["single"]["time"] =
-                self.times["times"][self.num_times() - 1]["time"].clone();
-            self.times["bests"]["single"]["scramble"] =
-                self.times["times"][self.num_times() - 1]["scramble"].clone();
-        }
+        // the new time is in the latest averages, and may be a best or in one
+        self.recalculate_averages();
+        self.recompute_bests();
+    }
 
-        // AO5
+    fn recalculate_averages(&mut self) {
+        // recalculate all ao5 and ao12
         for i in 0..self.num_times() {
             self.times["times"][i]["ao5"] = Value::from(self.calc_ao5(i));
-        }
-
-        // check if the new time is the best ao5
-        if (self.num_times() >= 5)
-            && (self.times["times"][self.num_times() - 1]["ao5"]
-                .as_f64()
-                .unwrap()
-                < self.times["bests"]["ao5"]["time"].as_f64().unwrap()
-                || self.times["bests"]["ao5"]["time"].as_f64().unwrap() == 0.0)
-        {
-            self.times["bests"]["ao5"]["time"] =
-                self.times["times"][self.num_times() - 1]["ao5"].clone();
-            self.times["bests"]["ao5"]["times"] = Value::from(vec![
-                self.times["times"][self.num_times() - 5].clone(),
-                self.times["times"][self.num_times() - 4].clone(),
-                self.times["times"][self.num_times() - 3].clone(),
-                self.times["times"][self.num_times() - 2].clone(),
-                self.times["times"][self.num_times() - 1].clone(),
-            ]);
-        }
-
-        // AO12
-        for i in 0..self.num_times() {
             self.times["times"][i]["ao12"] = Value::from(self.calc_ao12(i));
         }
-
-        // check if the new time is the best ao12
-        if (self.num_times() >= 12)
-            && (self.times["times"][self.num_times() - 1]["ao12"]
-                .as_f64()
-                .unwrap()
-                < self.times["bests"]["ao12"]["time"].as_f64().unwrap()
-                || self.times["bests"]["ao12"]["time"].as_f64().unwrap() == 0.0)
-        {
-            self.times["bests"]["ao12"]["time"] =
-                self.times["times"][self.num_times() - 1]["ao12"].clone();
-            // use a loop to make a vector of the last 12 times
-            let mut last_12_best: Vec<Value> = vec![];
-            for i in self.num_times() - 12..self.num_times() {
-                last_12_best.push(self.times["times"][i].clone());
-            }
-            self.times["bests"]["ao12"]["times"] = Value::from(last_12_best);
-        }
     }
 
     pub fn calc_ao5(&self, index: usize) -> f64 {
@@ -277,10 +187,11 @@ impl Times {
 
         let mut times = vec![];
         for i in index - 4..index + 1 {
-            times.push(self.times["times"][i]["time"].as_f64().unwrap());
-            // check for dnf
+            // check for dnf, which counts as the worst time
             if self.times["times"][i]["penalty"].as_str().unwrap() == "DNF" {
                 dnf_count += 1;
+            } else {
+                times.push(self.times["times"][i]["time"].as_f64().unwrap());
             }
         }
 
@@ -293,17 +204,9 @@ impl Times {
                 times.pop();
             }
             1 => {
-                // one dnf remove the best time
+                // one dnf, it is the worst and already left out, remove the best time
                 times.sort_by(|a, b| a.partial_cmp(b).unwrap());
                 times.remove(0);
-
-                // find the dnf and remove it
-                for i in 0..times.len() {
-                    if self.times["times"][i]["penalty"].as_str().unwrap() == "DNF" {
-                        times.remove(i);
-                        break;
-                    }
-                }
             }
             _ => {
                 // more than one dnf, ao5 is dnf
@@ -329,10 +232,11 @@ impl Times {
         // Calculate ao12 by cutting out best and worst times and averaging the rest
         let mut times = vec![];
         for i in index - 11..index + 1 {
-            times.push(self.times["times"][i]["time"].as_f64().unwrap());
-            // check for dnf
+            // check for dnf, which counts as the worst time
             if self.times["times"][i]["penalty"].as_str().unwrap() == "DNF" {
                 dnf_count += 1;
+            } else {
+                times.push(self.times["times"][i]["time"].as_f64().unwrap());
             }
         }
 
@@ -344,17 +248,9 @@ impl Times {
                 times.pop();
             }
             1 => {
-                // one dnf remove the best time
+                // one dnf, it is the worst and already left out, remove the best time
                 times.sort_by(|a, b| a.partial_cmp(b).unwrap());
                 times.remove(0);
-
-                // find the dnf and remove it
-                for i in 0..times.len() {
-                    if self.times["times"][i]["penalty"].as_str().unwrap() == "DNF" {
-                        times.remove(i);
-                        break;
-                    }
-                }
             }
             _ => {
                 // more than one dnf, ao12 is dnf
@@ -416,23 +312,15 @@ impl Times {
         }
 
         if self.num_times() >= 5 {
-            currents.push(format!(
-                "{:.3}",
-                self.times["times"][self.num_times() - 1]["ao5"]
-                    .as_f64()
-                    .unwrap()
-            ));
+            let average = self.times["times"][self.num_times() - 1]["ao5"].as_f64();
+            currents.push(self.display_time(average.unwrap()));
         } else {
             currents.push("NA".to_string());
         }
 
         if self.num_times() >= 12 {
-            currents.push(format!(
-                "{:.3}",
-                self.times["times"][self.num_times() - 1]["ao12"]
-                    .as_f64()
-                    .unwrap()
-            ));
+            let average = self.times["times"][self.num_times() - 1]["ao12"].as_f64();
+            currents.push(self.display_time(average.unwrap()));
         } else {
             currents.push("NA".to_string());
         }
@@ -446,25 +334,16 @@ impl Times {
 
         let mut bests = vec![];
 
-        bests.push(format!(
-            "{:.3}",
-            self.times["bests"]["single"]["time"].as_f64().unwrap()
-        ));
+        bests.push(self.display_time(self.times["bests"]["single"]["time"].as_f64().unwrap()));
 
         if self.num_times() >= 5 {
-            bests.push(format!(
-                "{:.3}",
-                self.times["bests"]["ao5"]["time"].as_f64().unwrap()
-            ));
+            bests.push(self.display_time(self.times["bests"]["ao5"]["time"].as_f64().unwrap()));
         } else {
             bests.push("NA".to_string());
         }
 
         if self.num_times() >= 12 {
-            bests.push(format!(
-                "{:.3}",
-                self.times["bests"]["ao12"]["time"].as_f64().unwrap()
-            ));
+            bests.push(self.display_time(self.times["bests"]["ao12"]["time"].as_f64().unwrap()));
         } else {
             bests.push("NA".to_string());
         }
@@ -474,64 +353,259 @@ impl Times {
 
     pub fn toggle_penalty(&mut self, penalty: Penalty) {
         // toggle penalty for the last time
-        if self.num_times() == 0 {
+        if self.num_times() == 0 || penalty == Penalty::None {
             return;
         }
 
         let index = self.num_times() - 1;
+        let penalty = match self.times["times"][index]["penalty"] == penalty.to_string() {
+            true => Penalty::None,
+            false => penalty,
+        };
+        self.set_penalty(index, penalty);
+    }
 
-        match penalty {
-            Penalty::DNF => match self.times["times"][index]["penalty"].as_str().unwrap() {
-                "DNF" => {
-                    self.times["times"][index]["penalty"] = Value::from("");
-                }
-                "+2" => {
-                    self.times["times"][index]["penalty"] = Value::from("DNF");
-                    self.times["times"][index]["time"] =
-                        Value::from(self.times["times"][index]["time"].as_f64().unwrap() - 2.0);
-                }
-                _ => {
-                    self.times["times"][index]["penalty"] = Value::from("DNF");
-                }
-            },
-            Penalty::PlusTwo => match self.times["times"][index]["penalty"].as_str().unwrap() {
-                "+2" => {
-                    self.times["times"][index]["penalty"] = Value::from("");
-                    self.times["times"][index]["time"] =
-                        Value::from(self.times["times"][index]["time"].as_f64().unwrap() - 2.0);
-                }
-                "DNF" => {
-                    self.times["times"][index]["penalty"] = Value::from("+2");
-                    self.times["times"][index]["time"] =
-                        Value::from(self.times["times"][index]["time"].as_f64().unwrap() + 2.0);
-                }
-                _ => {
-                    self.times["times"][index]["penalty"] = Value::from("+2");
-                    self.times["times"][index]["time"] =
-                        Value::from(self.times["times"][index]["time"].as_f64().unwrap() + 2.0);
-                }
-            },
-            Penalty::None => {}
+    pub fn set_penalty(&mut self, index: usize, penalty: Penalty) {
+        // set the penalty of a time, which for a +2 is kept in the time
+        if index >= self.num_times() {
+            return;
         }
 
-        // recalculate all a05 and ao12
-        for i in 0..self.num_times() {
-            self.times["times"][i]["ao5"] = Value::from(self.calc_ao5(i));
-            self.times["times"][i]["ao12"] = Value::from(self.calc_ao12(i));
+        let time = self.times["times"][index]["time"].as_f64().unwrap();
+        let plus_two = self.times["times"][index]["penalty"] == "+2";
+        let time = match (plus_two, penalty == Penalty::PlusTwo) {
+            (true, false) => time - 2.0,
+            (false, true) => time + 2.0,
+            _ => time,
+        };
+        self.times["times"][index]["time"] = Value::from(time);
+        self.times["times"][index]["penalty"] = Value::from(penalty.to_string());
+
+        // recalculate the averages, and the bests they and the time were in
+        self.recalculate_averages();
+        self.recompute_bests();
+    }
+
+    pub fn recompute_bests(&mut self) {
+        // find the bests again from the stored times and averages, leaving out
+        // DNF singles and averages
+        self.reset_bests();
+        let times = self.times["times"].as_array().unwrap().clone();
+
+        let singles = times.iter().filter(|time| time["penalty"] != "DNF");
+        let best =
+            singles.min_by(|a, b| a["time"].as_f64().partial_cmp(&b["time"].as_f64()).unwrap());
+        if let Some(best) = best {
+            self.times["bests"]["single"]["time"] = best["time"].clone();
+            self.times["bests"]["single"]["scramble"] = best["scramble"].clone();
+        }
+
+        for (key, size) in [("ao5", 5), ("ao12", 12)] {
+            if times.len() >= size {
+                // a DNF until there is an average that isn't
+                self.times["bests"][key]["time"] = Value::from(-1.0);
+            }
+            let mut best: Option<(usize, f64)> = None;
+            for (i, time) in times.iter().enumerate().skip(size - 1) {
+                let average = time[key].as_f64().unwrap_or(-1.0);
+                if average > 0.0 && best.map_or(true, |(_, b)| average < b) {
+                    best = Some((i, average));
+                }
+            }
+            if let Some((i, average)) = best {
+                self.times["bests"][key]["time"] = Value::from(average);
+                self.times["bests"][key]["times"] = Value::from(times[i + 1 - size..=i].to_vec());
+            }
         }
     }
 
     pub fn display_time(&self, time: f64) -> String {
-        let time_string;
-
         if time == -1.0 {
-            time_string = "DNF".to_string();
+            "DNF".to_string()
         } else if time == 0.0 {
-            time_string = "NA".to_string();
+            "NA".to_string()
         } else {
-            time_string = format!("{:.3}", time);
+            format!("{:.3}", time)
+        }
+    }
+
+    pub fn variant(&self) -> Variant {
+        // event variant the session is scrambled for, 3x3 for older files
+        self.times["session"]["event"]
+            .as_str()
+            .and_then(Variant::from_id)
+            .unwrap_or(Variant::ThreeByThree)
+    }
+
+    pub fn event(&self, index: usize) -> Variant {
+        // event a solve was done in, the session's for solves saved without one
+        self.times["times"][index]["event"]
+            .as_str()
+            .and_then(Variant::from_id)
+            .unwrap_or(self.variant())
+    }
+
+    pub fn set_variant(&mut self, variant: Variant) {
+        // the times and bests at the top are the current event's, the other
+        // events' are put away under "events" until they are switched back to
+        let current = self.variant();
+        if variant != current {
+            let times = self.times["times"].take();
+            let bests = self.times["bests"].take();
+            self.times["events"][current.id()] = json!({ "times": times, "bests": bests });
+
+            let mut event = self.times["events"]
+                .as_object_mut()
+                .and_then(|events| events.remove(variant.id()))
+                .unwrap_or_else(|| serde_json::from_str(BASE_JSON).unwrap());
+            self.times["times"] = event["times"].take();
+            self.times["bests"] = event["bests"].take();
+        }
+        self.times["session"]["event"] = Value::from(variant.id());
+    }
+
+    pub fn subset(&self) -> Subset {
+        // training subset the session drills, full scrambles for older files
+        self.times["session"]["subset"]
+            .as_str()
+            .and_then(Subset::from_id)
+            .unwrap_or(Subset::None)
+    }
+
+    pub fn set_subset(&mut self, subset: Subset) {
+        self.times["session"]["subset"] = Value::from(subset.id());
+    }
+
+    pub fn inspection(&self, variant: Variant) -> Inspection {
+        // inspection the session uses for an event, its default until one is picked
+        Inspection::from_value(&self.times["session"]["inspection"][variant.id()])
+            .unwrap_or(Inspection::default_for(variant))
+    }
+
+    pub fn set_inspection(&mut self, variant: Variant, inspection: Inspection) {
+        self.times["session"]["inspection"][variant.id()] = inspection.to_value();
+    }
+
+    pub fn set_last(&mut self, key: &str, value: Value) {
+        // set an extra field on the last time
+        if self.num_times() == 0 {
+            return;
+        }
+        let index = self.num_times() - 1;
+        self.times["times"][index][key] = value;
+    }
+
+    pub fn add_trainer_time(&mut self, case: &str, time: f64) {
+        // trainer executions are kept per case, apart from the session times
+        let case = &mut self.times["trainer"][case];
+        if case["times"].as_array().is_none() {
+            case["times"] = json!([]);
+        }
+        case["times"]
+            .as_array_mut()
+            .unwrap()
+            .push(Value::from(time));
+    }
+
+    pub fn add_trainer_miss(&mut self, case: &str) {
+        // count an attempt where the case wasn't recognised
+        let misses = &mut self.times["trainer"][case]["misses"];
+        *misses = Value::from(misses.as_u64().unwrap_or(0) + 1);
+    }
+
+    pub fn trainer_stats(&self, case: &str) -> CaseStats {
+        let case = &self.times["trainer"][case];
+        let times: Vec<f64> = case["times"]
+            .as_array()
+            .map(|times| times.iter().filter_map(Value::as_f64).collect())
+            .unwrap_or_default();
+        let misses = case["misses"].as_u64().unwrap_or(0) as usize;
+        if times.is_empty() {
+            return CaseStats {
+                misses,
+                ..CaseStats::default()
+            };
+        }
+        CaseStats {
+            count: times.len(),
+            mean: times.iter().sum::<f64>() / times.len() as f64,
+            best: times.iter().copied().fold(f64::MAX, f64::min),
+            misses,
+        }
+    }
+
+    pub fn schedule(&self, case: &str) -> Option<Schedule> {
+        // review schedule of a trainer case, `None` until it is first reviewed
+        Schedule::from_value(&self.times["trainer"][case]["schedule"])
+    }
+
+    pub fn set_schedule(&mut self, case: &str, schedule: Schedule) {
+        self.times["trainer"][case]["schedule"] = schedule.to_value();
+    }
+
+    pub fn add_mbld(&mut self, result: MbldResult, scrambles: &[Scramble]) {
+        // multi-blind attempts are kept apart from the session times
+        let attempts = &mut self.times["mbld"]["attempts"];
+        if attempts.as_array().is_none() {
+            *attempts = json!([]);
+        }
+        let mut attempt = result.to_value();
+        attempt["scrambles"] = scrambles.iter().map(|s| s.to_string()).collect();
+        attempts.as_array_mut().unwrap().push(attempt);
+    }
+
+    pub fn mbld_attempts(&self) -> Vec<MbldResult> {
+        // multi-blind attempts, oldest first
+        self.times["mbld"]["attempts"]
+            .as_array()
+            .map(|attempts| attempts.iter().filter_map(MbldResult::from_value).collect())
+            .unwrap_or_default()
+    }
+
+    pub fn mbld_best(&self) -> Option<MbldResult> {
+        // best multi-blind attempt, DNFs never are
+        self.mbld_attempts()
+            .into_iter()
+            .filter(|attempt| !attempt.dnf())
+            .min_by(|a, b| a.rank(b))
+    }
+
+    pub fn reconstruction(&self, index: usize) -> Option<Reconstruction> {
+        // moves the solve was done with, if it has been reconstructed
+        Reconstruction::from_value(&self.times["times"][index]["reconstruction"])
+    }
+
+    pub fn set_reconstruction(&mut self, index: usize, reconstruction: &Reconstruction) {
+        if index >= self.num_times() {
+            return;
         }
+        self.times["times"][index]["reconstruction"] = reconstruction.to_value();
+    }
 
-        return time_string;
+    pub fn phase_averages(&self) -> Vec<(String, f64)> {
+        // mean time of each phase over the solves timed in phases, DNFs left out
+        let mut phases: Vec<(String, f64, usize)> = vec![];
+        for solve in self.times["times"].as_array().unwrap() {
+            if solve["penalty"].as_str() == Some("DNF") {
+                continue;
+            }
+            for split in solve["splits"].as_array().into_iter().flatten() {
+                let (Some(phase), Some(time)) = (split["phase"].as_str(), split["time"].as_f64())
+                else {
+                    continue;
+                };
+                match phases.iter_mut().find(|(name, _, _)| name == phase) {
+                    Some((_, total, count)) => {
+                        *total += time;
+                        *count += 1;
+                    }
+                    None => phases.push((phase.to_string(), time, 1)),
+                }
+            }
+        }
+        phases
+            .into_iter()
+            .map(|(phase, total, count)| (phase, total / count as f64))
+            .collect()
     }
 }
@@ -0,0 +1,252 @@
+use crate::app::AppResult;
+use crate::cube::Cube;
+use crate::cubie::{invert, CubieCube};
+use crate::notation::parse_moves;
+use crate::schedule::Schedule;
+use crate::scramble::Scramble;
+use crate::solver;
+use crate::times::Times;
+use crate::turns::{Direction, Move, Turn};
+use home;
+use rand::Rng;
+use serde_json::{json, Value};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// PLLs written to the case file the first time the trainer is opened.
+const DEFAULT_CASES: [(&str, &str); 21] = [
+    ("Aa", "R' F R' B2 R F' R' B2 R2"),
+    ("Ab", "R2 B2 R F R' B2 R F' R"),
+    ("E", "R B' R' F R B R' F' R B R' F R B' R' F'"),
+    ("F", "R' U' F' R U R' U' R' F R2 U' R' U' R U R' U R"),
+    ("Ga", "R2 U R' U R' U' R U' R2 U' D R' U R D'"),
+    ("Gb", "R' U' R U D' R2 U R' U R U' R U' R2 D"),
+    ("Gc", "R2 U' R U' R U R' U R2 U D' R U' R' D"),
+    ("Gd", "R U R' U' D R2 U' R U' R' U R' U R2 D'"),
+    ("H", "M2 U M2 U2 M2 U M2"),
+    ("Ja", "R' U L' U2 R U' R' U2 R L"),
+    ("Jb", "R U R' F' R U R' U' R' F R2 U' R'"),
+    (
+        "Na",
+        "R U R' U R U R' F' R U R' U' R' F R2 U' R' U2 R U' R'",
+    ),
+    ("Nb", "R' U R U' R' F' U' F R U R' F R' F' R U' R"),
+    ("Ra", "R U' R' U' R U R D R' U' R D' R' U2 R'"),
+    ("Rb", "R2 F R U R U' R' F' R U2 R' U2 R"),
+    ("T", "R U R' U' R' F R2 U' R' U' R U R' F'"),
+    ("Ua", "M2 U M U2 M' U M2"),
+    ("Ub", "M2 U' M U2 M' U' M2"),
+    ("V", "R U' R U R' D R D' R U' D R2 U R2 D' R2"),
+    ("Y", "F R U' R' U' R U R' F' R U R' U' R' F R F'"),
+    ("Z", "M' U M2 U M2 U M' U2 M2"),
+];
+
+/// An algorithm case to drill.
+#[derive(Debug, Clone)]
+pub struct Case {
+    pub name: String,
+    /// Moves that set the case up from solved, in solving orientation.
+    pub setup: Vec<Move>,
+    /// Preferred solutions, shown after each attempt.
+    pub solutions: Vec<String>,
+}
+
+/// Algorithm trainer: a set of cases, the ones picked to drill, and the case
+/// being drilled.
+#[derive(Debug)]
+pub struct Trainer {
+    pub cases: Vec<Case>,
+    pub selected: Vec<bool>,
+    /// Row of the case list the cursor is on while picking cases.
+    pub cursor: usize,
+    /// Whether the case list is open.
+    pub choosing: bool,
+    /// Case the current scramble sets up.
+    pub current: Option<usize>,
+    /// Case of the last attempt.
+    pub last: Option<usize>,
+    /// Schedule of the last case before its review, to grade it again if it
+    /// wasn't recognised. Extra practice isn't reviewed, so has none.
+    pub undo: Option<Schedule>,
+    /// Whether the last attempt can still be recorded as not recognised.
+    pub missable: bool,
+}
+
+impl Trainer {
+    /// Path of the default case file, next to the times file.
+    pub fn default_path() -> PathBuf {
+        let mut path = PathBuf::new();
+        path.push(home::home_dir().unwrap());
+        path.push(".cargo/bin/cube-times");
+        path.push("cases.json");
+        path
+    }
+
+    /// Loads cases from a file, creating it with the PLLs if it doesn't exist.
+    ///
+    /// The file holds a `cases` array of objects with a `name`, a `setup`
+    /// algorithm and a list of `solutions`.
+    pub fn load(path: &Path) -> AppResult<Self> {
+        if !path.exists() {
+            fs::create_dir_all(path.parent().unwrap())?;
+            fs::write(path, serde_json::to_string_pretty(&default_cases())?)?;
+        }
+        let contents = fs::read_to_string(path)
+            .map_err(|e| format!("couldn't read {}: {}", path.display(), e))?;
+        let json: Value = serde_json::from_str(&contents)
+            .map_err(|e| format!("couldn't parse {}: {}", path.display(), e))?;
+        Self::from_json(&json)
+    }
+
+    pub fn from_json(json: &Value) -> AppResult<Self> {
+        let mut cases = vec![];
+        for (i, case) in json["cases"]
+            .as_array()
+            .ok_or("expected a cases array")?
+            .iter()
+            .enumerate()
+        {
+            let name = case["name"]
+                .as_str()
+                .ok_or(format!("case {} has no name", i + 1))?;
+            let setup = case["setup"]
+                .as_str()
+                .ok_or(format!("case {} has no setup", name))?;
+            let setup = parse_moves(setup).map_err(|e| format!("case {}: {}", name, e))?;
+            let solutions = case["solutions"]
+                .as_array()
+                .map(|solutions| {
+                    solutions
+                        .iter()
+                        .filter_map(|s| s.as_str().map(String::from))
+                        .collect()
+                })
+                .unwrap_or_default();
+            cases.push(Case {
+                name: name.to_string(),
+                setup,
+                solutions,
+            });
+        }
+        if cases.is_empty() {
+            return Err("no cases to train".into());
+        }
+
+        Ok(Self {
+            selected: vec![true; cases.len()],
+            cases,
+            cursor: 0,
+            choosing: false,
+            current: None,
+            last: None,
+            undo: None,
+            missable: false,
+        })
+    }
+
+    /// Picks the next selected case and a scramble that sets it up, `None`
+    /// if no case is selected.
+    ///
+    /// Cases due for review come first, then cases never reviewed; once
+    /// neither are left any selected case is picked.
+    pub fn next_case<R: Rng + ?Sized>(
+        &mut self,
+        rng: &mut R,
+        times: &Times,
+        now: u64,
+    ) -> Option<Scramble> {
+        let selected: Vec<usize> = (0..self.cases.len())
+            .filter(|&i| self.selected[i])
+            .collect();
+        let schedules: Vec<Option<Schedule>> = selected
+            .iter()
+            .map(|&i| times.schedule(&self.cases[i].name))
+            .collect();
+        let due: Vec<usize> = selected
+            .iter()
+            .zip(&schedules)
+            .filter(|(_, schedule)| schedule.is_some_and(|s| s.is_due(now)))
+            .map(|(&i, _)| i)
+            .collect();
+        let new: Vec<usize> = selected
+            .iter()
+            .zip(&schedules)
+            .filter(|(_, schedule)| schedule.is_none())
+            .map(|(&i, _)| i)
+            .collect();
+        let pool = [due, new, selected]
+            .into_iter()
+            .find(|pool| !pool.is_empty());
+        let Some(pool) = pool else {
+            self.current = None;
+            return None;
+        };
+        let case = pool[rng.gen_range(0..pool.len())];
+        self.current = Some(case);
+        Some(setup_scramble(&self.cases[case], rng))
+    }
+
+    /// Toggles whether the case under the cursor is drilled.
+    pub fn toggle_selected(&mut self) {
+        self.selected[self.cursor] = !self.selected[self.cursor];
+    }
+
+    /// Selects every case, or none if they are all selected.
+    pub fn toggle_all(&mut self) {
+        let all = self.selected.iter().all(|&s| s);
+        self.selected.iter_mut().for_each(|s| *s = !all);
+    }
+
+    pub fn move_cursor(&mut self, down: bool) {
+        self.cursor = match down {
+            true => (self.cursor + 1) % self.cases.len(),
+            false => (self.cursor + self.cases.len() - 1) % self.cases.len(),
+        };
+    }
+}
+
+fn random_auf<R: Rng + ?Sized>(rng: &mut R) -> Option<Move> {
+    let turn = Turn::from_quarter_turns(rng.gen_range(0..4))?;
+    Some(Move::new(Direction::Up, turn))
+}
+
+/// Scramble that sets up a case with a random AUF before and after it.
+///
+/// Cases are written with the last layer on top, but like the subsets the
+/// scramble puts it on the yellow D layer, so the setup is done upside down.
+/// The state is solved with the two-phase solver so the scramble doesn't give
+/// the algorithm away; setups that can't be read as a 3x3 are used as they are.
+pub fn setup_scramble<R: Rng + ?Sized>(case: &Case, rng: &mut R) -> Scramble {
+    let flip = Move::rotation(Direction::Front, Turn::Double);
+    let mut moves = vec![flip];
+    moves.extend(random_auf(rng));
+    moves.extend(&case.setup);
+    moves.extend(random_auf(rng));
+    moves.push(flip);
+
+    let state = CubieCube::from_cube(&Cube::scrambled(3, &moves));
+    match state.and_then(|state| solver::solve(&state, 24, solver::BUDGET)) {
+        Some(solution) => Scramble {
+            moves: invert(&solution),
+        },
+        None => Scramble { moves },
+    }
+}
+
+/// The built in PLL cases, each set up by the inverse of its solution.
+fn default_cases() -> Value {
+    let cases: Vec<Value> = DEFAULT_CASES
+        .iter()
+        .map(|(name, solution)| {
+            let setup = Scramble {
+                moves: invert(&parse_moves(solution).expect("built in cases parse")),
+            };
+            json!({
+                "name": name,
+                "setup": setup.to_string().trim(),
+                "solutions": [solution],
+            })
+        })
+        .collect();
+    json!({ "cases": cases })
+}
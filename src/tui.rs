@@ -1,12 +1,16 @@
 use crate::app::{App, AppResult};
 use crate::event::EventHandler;
+use crate::input::InputMode;
 use crate::ui;
 use crossterm::event::{
-    DisableMouseCapture, EnableMouseCapture, KeyboardEnhancementFlags, PopKeyboardEnhancementFlags,
+    DisableBracketedPaste, DisableFocusChange, DisableMouseCapture, EnableBracketedPaste,
+    EnableFocusChange, EnableMouseCapture, KeyboardEnhancementFlags, PopKeyboardEnhancementFlags,
     PushKeyboardEnhancementFlags,
 };
+use crossterm::style::Print;
 use crossterm::terminal::{self, EnterAlternateScreen, LeaveAlternateScreen};
 use std::io;
+use std::mem;
 use std::panic;
 use tui::backend::Backend;
 use tui::Terminal;
@@ -21,31 +25,47 @@ pub struct Tui<B: Backend> {
     terminal: Terminal<B>,
     /// Terminal event handler.
     pub events: EventHandler,
+    /// Whether key releases were asked for with kitty's keyboard protocol.
+    enhanced: bool,
 }
 
 impl<B: Backend> Tui<B> {
     /// Constructs a new instance of [`Tui`].
     pub fn new(terminal: Terminal<B>, events: EventHandler) -> Self {
-        Self { terminal, events }
+        Self {
+            terminal,
+            events,
+            enhanced: false,
+        }
     }
 
     /// Initializes the terminal interface.
     ///
-    /// It enables the raw mode and sets terminal properties.
-    pub fn init(&mut self) -> AppResult<()> {
+    /// It enables the raw mode and sets terminal properties. Key releases are
+    /// only asked for when space is read from the terminal as held and let go.
+    pub fn init(&mut self, input: InputMode) -> AppResult<()> {
         terminal::enable_raw_mode()?;
         crossterm::execute!(
             io::stderr(),
             EnterAlternateScreen,
             EnableMouseCapture,
-            PushKeyboardEnhancementFlags(KeyboardEnhancementFlags::REPORT_EVENT_TYPES)
+            EnableFocusChange,
+            EnableBracketedPaste
         )?;
+        self.enhanced = input == InputMode::Release;
+        if self.enhanced {
+            crossterm::execute!(
+                io::stderr(),
+                PushKeyboardEnhancementFlags(KeyboardEnhancementFlags::REPORT_EVENT_TYPES)
+            )?;
+        }
 
         // Define a custom panic hook to reset the terminal properties.
         // This way, you won't have your terminal messed up if an unexpected error happens.
         let panic_hook = panic::take_hook();
+        let enhanced = self.enhanced;
         panic::set_hook(Box::new(move |panic| {
-            Self::reset().expect("failed to reset the terminal");
+            Self::reset(enhanced).expect("failed to reset the terminal");
             panic_hook(panic);
         }));
 
@@ -54,12 +74,16 @@ impl<B: Backend> Tui<B> {
         Ok(())
     }
 
-    /// [`Draw`] the terminal interface by [`rendering`] the widgets.
+    /// [`Draw`] the terminal interface by [`rendering`] the widgets, and
+    /// ring the bell if the app asked for it.
     ///
     /// [`Draw`]: tui::Terminal::draw
     /// [`rendering`]: crate::ui:render
     pub fn draw(&mut self, app: &mut App) -> AppResult<()> {
         self.terminal.draw(|frame| ui::render(app, frame))?;
+        if mem::take(&mut app.bell) {
+            crossterm::execute!(io::stderr(), Print('\x07'))?;
+        }
         Ok(())
     }
 
@@ -67,22 +91,28 @@ impl<B: Backend> Tui<B> {
     ///
     /// This function is also used for the panic hook to revert
     /// the terminal properties if unexpected errors occur.
-    fn reset() -> AppResult<()> {
+    fn reset(enhanced: bool) -> AppResult<()> {
         terminal::disable_raw_mode()?;
+        if enhanced {
+            crossterm::execute!(io::stdout(), PopKeyboardEnhancementFlags)?;
+        }
         crossterm::execute!(
             io::stdout(),
             LeaveAlternateScreen,
             DisableMouseCapture,
-            PopKeyboardEnhancementFlags
+            DisableFocusChange,
+            DisableBracketedPaste
         )?;
         Ok(())
     }
 
     /// Exits the terminal interface.
     ///
-    /// It disables the raw mode and reverts back the terminal properties.
+    /// It stops reading events, disables the raw mode and reverts back the
+    /// terminal properties.
     pub fn exit(&mut self) -> AppResult<()> {
-        Self::reset()?;
+        self.events.stop();
+        Self::reset(self.enhanced)?;
         self.terminal.show_cursor()?;
         Ok(())
     }
@@ -1,7 +1,7 @@
 use rand::Rng;
 use std::fmt::Display;
 
-#[derive(PartialEq, Debug)]
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Copy)]
 pub enum Direction {
     Up,
     Down,
@@ -37,7 +37,7 @@ impl Display for Direction {
     }
 }
 
-#[derive(PartialEq, Debug)]
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Copy)]
 pub enum Turn {
     Clockwise,
     CounterClockwise,
@@ -52,6 +52,25 @@ impl Turn {
             Turn::Double => Turn::Double,
         }
     }
+
+    /// Number of clockwise quarter turns.
+    pub fn quarter_turns(&self) -> u8 {
+        match self {
+            Turn::Clockwise => 1,
+            Turn::Double => 2,
+            Turn::CounterClockwise => 3,
+        }
+    }
+
+    /// Turn for a number of clockwise quarter turns, `None` if it is a multiple of four.
+    pub fn from_quarter_turns(quarter_turns: u8) -> Option<Self> {
+        match quarter_turns % 4 {
+            1 => Some(Turn::Clockwise),
+            2 => Some(Turn::Double),
+            3 => Some(Turn::CounterClockwise),
+            _ => None,
+        }
+    }
 }
 
 impl Display for Turn {
@@ -64,15 +83,65 @@ impl Display for Turn {
     }
 }
 
-#[derive(PartialEq, Debug)]
+/// Which layers of the puzzle a move turns.
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Copy)]
+pub enum Layers {
+    /// Only the outer face, e.g. `R`.
+    Outer,
+    /// The outer face plus inner layers, `n` layers deep in total, e.g. `Rw` (2) or `3Rw` (3).
+    Wide(u8),
+    /// The middle layer next to the direction's face, e.g. `M` follows `L`.
+    Slice,
+    /// The whole puzzle, following the direction's face, e.g. `x` follows `R`.
+    Rotation,
+}
+
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Copy)]
 pub struct Move {
     pub direction: Direction,
     pub turn: Turn,
+    pub layers: Layers,
 }
 
 impl Move {
-    pub fn random() -> Self {
-        let mut rng = rand::thread_rng();
+    pub fn new(direction: Direction, turn: Turn) -> Self {
+        Move {
+            direction,
+            turn,
+            layers: Layers::Outer,
+        }
+    }
+
+    pub fn wide(direction: Direction, turn: Turn, depth: u8) -> Self {
+        Move {
+            direction,
+            turn,
+            layers: Layers::Wide(depth),
+        }
+    }
+
+    pub fn slice(direction: Direction, turn: Turn) -> Self {
+        Move {
+            direction,
+            turn,
+            layers: Layers::Slice,
+        }
+    }
+
+    pub fn rotation(direction: Direction, turn: Turn) -> Self {
+        Move {
+            direction,
+            turn,
+            layers: Layers::Rotation,
+        }
+    }
+
+    pub fn random<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        Self::random_layers(rng, &[Layers::Outer])
+    }
+
+    /// Random move whose layers are picked from `choices`.
+    pub fn random_layers<R: Rng + ?Sized>(rng: &mut R, choices: &[Layers]) -> Self {
         let direction = match rng.gen_range(0..6) {
             0 => Direction::Up,
             1 => Direction::Down,
@@ -88,6 +157,63 @@ impl Move {
             2 => Turn::Double,
             _ => panic!("Random number generator failed"),
         };
-        Move { direction, turn }
+        let layers = choices[rng.gen_range(0..choices.len())];
+        Move {
+            direction,
+            turn,
+            layers,
+        }
+    }
+}
+
+impl Move {
+    /// The same move written against the face its notation is named after:
+    /// slices follow L, D and F, rotations follow R, U and F. Face turns are
+    /// returned unchanged.
+    pub fn canonical(&self) -> Self {
+        let named_after = match (self.layers, self.direction) {
+            (Layers::Slice, Direction::Right) => Direction::Left,
+            (Layers::Slice, Direction::Up) => Direction::Down,
+            (Layers::Slice, Direction::Back) => Direction::Front,
+            (Layers::Rotation, Direction::Left) => Direction::Right,
+            (Layers::Rotation, Direction::Down) => Direction::Up,
+            (Layers::Rotation, Direction::Back) => Direction::Front,
+            _ => return *self,
+        };
+        Move {
+            direction: named_after,
+            turn: self.turn.opposite(),
+            layers: self.layers,
+        }
+    }
+
+    /// Whether both moves turn about the same axis, so they commute.
+    pub fn same_axis(&self, other: &Move) -> bool {
+        self.direction == other.direction || self.direction == other.direction.opposite()
+    }
+}
+
+impl Display for Move {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.layers {
+            Layers::Outer => write!(f, "{}{}", self.direction, self.turn),
+            Layers::Wide(2) => write!(f, "{}w{}", self.direction, self.turn),
+            Layers::Wide(depth) => write!(f, "{}{}w{}", depth, self.direction, self.turn),
+            Layers::Slice | Layers::Rotation => {
+                // Slices and rotations are named after one face of their axis
+                // (M after L, x after R, ...), so moves following the other
+                // face are written as the inverse.
+                let canonical = self.canonical();
+                let name = match (self.layers, canonical.direction) {
+                    (Layers::Slice, Direction::Left) => "M",
+                    (Layers::Slice, Direction::Down) => "E",
+                    (Layers::Slice, _) => "S",
+                    (_, Direction::Right) => "x",
+                    (_, Direction::Up) => "y",
+                    _ => "z",
+                };
+                write!(f, "{}{}", name, canonical.turn)
+            }
+        }
     }
 }
@@ -1,14 +1,22 @@
 use tui::{
     backend::Backend,
     layout::Alignment,
-    layout::{Constraint, Direction, Layout, Rect},
+    layout::{Constraint, Direction, Layout, Margin, Rect},
     style::{Color, Modifier, Style},
     text::{self, Line, Span},
-    widgets::{Block, BorderType, Borders, Clear, Padding, Paragraph, Row, Table, Wrap},
+    widgets::{
+        Block, BorderType, Borders, Clear, Padding, Paragraph, Row, Table, TableState, Wrap,
+    },
     Frame,
 };
 
-use crate::app::App;
+use crate::app::{App, Areas, Mode, Penalty};
+use crate::input::InputMode;
+use crate::keymap::{Action, Keymap};
+use crate::mbld::{self, Mbld};
+use crate::net::Net;
+use crate::schedule;
+use crate::scramble::Scramble;
 
 /// Renders the user interface widgets.
 pub fn render<B: Backend>(app: &mut App, frame: &mut Frame<'_, B>) {
@@ -17,28 +25,63 @@ pub fn render<B: Backend>(app: &mut App, frame: &mut Frame<'_, B>) {
     // - https://docs.rs/ratatui/latest/ratatui/widgets/index.html
     // - https://github.com/ratatui-org/ratatui/tree/master/examples
 
+    // Long scrambles (4BLD, 5BLD) wrap onto more than one line
+    let scramble_width = frame.size().width.saturating_sub(2).max(1);
+    let scramble_lines = (scramble_text(app).len() as u16 + scramble_width - 1) / scramble_width;
+
+    // Per-phase averages get a line of their own
+    let stats_lines = match app.config.phases.len() > 1 && app.mode == Mode::Timer {
+        true => 2,
+        false => 1,
+    };
+
     // Create a vertical layout
     let main_chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints(
             [
-                Constraint::Length(3),
-                Constraint::Length(3),
+                Constraint::Length(scramble_lines.max(1) + 2),
+                Constraint::Length(stats_lines + 2),
                 Constraint::Percentage(80),
             ]
             .as_ref(),
         )
         .split(frame.size());
 
-    // Render the block
+    // Render the block, noting where the parts that can be clicked go
+    app.areas = Areas::default();
     render_scramble(app, frame, main_chunks[0]);
     render_basic_stats(app, frame, main_chunks[1]);
     render_stats_timer(app, frame, main_chunks[2]);
     if app.show_help {
-        let area = centered_rect(40, 40, frame.size());
+        let area = centered_rect(50, 70, frame.size());
+
+        frame.render_widget(Clear, area);
+        frame.render_widget(help_msg(app.input, &app.config.keymap), area);
+    }
+    if let Some(index) = app.detail {
+        let area = centered_rect(60, 60, frame.size());
+
+        frame.render_widget(Clear, area);
+        render_detail(app, frame, area, index);
+    }
+    if let Some(input) = &app.time_input {
+        let area = centered_rect(50, 20, frame.size());
 
         frame.render_widget(Clear, area);
-        frame.render_widget(help_msg(), area);
+        frame.render_widget(time_entry_msg(input), area);
+    }
+    if let Some(mbld) = app.mbld.as_ref().filter(|mbld| mbld.input.is_some()) {
+        let area = centered_rect(50, 20, frame.size());
+
+        frame.render_widget(Clear, area);
+        frame.render_widget(mbld_msg(mbld), area);
+    }
+    if app.trainer.as_ref().is_some_and(|trainer| trainer.choosing) {
+        let area = centered_rect(30, 60, frame.size());
+
+        frame.render_widget(Clear, area);
+        render_case_list(app, frame, area);
     }
 }
 
@@ -68,7 +111,7 @@ fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
         .split(popup_layout[1])[1]
 }
 
-fn help_msg() -> Paragraph<'static> {
+fn help_msg(input: InputMode, keymap: &Keymap) -> Paragraph<'static> {
     let popup_block = Block::default()
         .title(Span::styled(
             "Help",
@@ -76,55 +119,329 @@ fn help_msg() -> Paragraph<'static> {
         ))
         .borders(Borders::ALL);
 
-    let text = vec![
-        text::Line::from(vec![
-            Span::styled("Space: ", Style::default().fg(Color::LightYellow)),
-            Span::from("Start/Stop timer"),
-        ]),
-        text::Line::from(vec![
-            Span::styled("q, Ctrl-C, Esc: ", Style::default().fg(Color::LightYellow)),
-            Span::from("Quit"),
-        ]),
-        text::Line::from(vec![
-            Span::styled("h: ", Style::default().fg(Color::LightYellow)),
-            Span::from("Toggle help"),
-        ]),
-        text::Line::from(vec![
-            Span::styled("s: ", Style::default().fg(Color::LightYellow)),
-            Span::from("New scramble"),
-        ]),
-        text::Line::from(vec![
-            Span::styled("d: ", Style::default().fg(Color::LightYellow)),
-            Span::from("Delete last time"),
-        ]),
-        text::Line::from(vec![
-            Span::styled("r: ", Style::default().fg(Color::LightYellow)),
-            Span::from("Reset"),
-        ]),
-        text::Line::from(vec![
-            Span::styled("l: ", Style::default().fg(Color::LightYellow)),
-            Span::from("Toggle last scramble"),
-        ]),
+    let mut text = vec![text::Line::from(vec![
+        Span::styled("Space: ", Style::default().fg(Color::LightYellow)),
+        Span::from(match input {
+            InputMode::Arm => "Press to get ready, again to start, any key to stop",
+            _ => "Hold until green, let go to start, any key to stop",
+        }),
+    ])];
+    // the rest follows the key bindings, leaving out actions without keys
+    for action in Action::ALL {
+        let mut keys = keymap.describe(action);
+        if action == Action::Quit {
+            keys = [keys, "Ctrl-C".to_string()].join(", ");
+        }
+        if keys.is_empty() {
+            continue;
+        }
+        text.push(text::Line::from(vec![
+            Span::styled(
+                format!("{}: ", keys),
+                Style::default().fg(Color::LightYellow),
+            ),
+            Span::from(action.description()),
+        ]));
+    }
+    Paragraph::new(text)
+        .block(popup_block)
+        .wrap(Wrap { trim: true })
+        .alignment(Alignment::Center)
+}
+
+// Render the detail popup of a solve, with its reconstruction played back on a net
+fn render_detail<B: Backend>(app: &mut App, frame: &mut Frame<B>, area: Rect, index: usize) {
+    let reconstruction = app.detail_reconstruction();
+    let Some(reconstruction) = reconstruction.filter(|_| app.reconstruction_input.is_none()) else {
+        frame.render_widget(detail_msg(app, index), area);
+        if app.reconstruction_input.is_none() {
+            render_penalty_buttons(app, frame, area, index);
+        }
+        return;
+    };
+
+    let detail_layout = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(60), Constraint::Percentage(40)].as_ref())
+        .split(area);
+    frame.render_widget(detail_msg(app, index), detail_layout[0]);
+    render_penalty_buttons(app, frame, detail_layout[0], index);
+
+    let shown = match app.playback {
+        0 => "scrambled".to_string(),
+        n => format!(
+            "{}/{}: {}",
+            n,
+            reconstruction.moves.len(),
+            reconstruction.moves[n - 1].m
+        ),
+    };
+    let net_block = Block::default()
+        .title(Span::styled(
+            format!("Move {} (Left/Right)", shown),
+            Style::default().fg(Color::LightYellow),
+        ))
+        .borders(Borders::ALL);
+    let scramble = app.detail_scramble().unwrap_or(Scramble { moves: vec![] });
+    let size = app.detail_event().puzzle_size();
+    let cube = reconstruction.cube_after(&scramble.moves, size, app.playback);
+    let net = Net::new(&cube, app.config.colour_scheme).block(net_block);
+
+    frame.render_widget(net, detail_layout[1]);
+}
+
+// Render buttons setting the solve's penalty along the bottom of the detail popup
+fn render_penalty_buttons<B: Backend>(
+    app: &mut App,
+    frame: &mut Frame<B>,
+    area: Rect,
+    index: usize,
+) {
+    let current = app.times.times["times"][index]["penalty"].clone();
+    let buttons = [
+        (Penalty::None, "[ OK ]"),
+        (Penalty::PlusTwo, "[ +2 ]"),
+        (Penalty::DNF, "[ DNF ]"),
+    ];
+    let width: u16 = buttons
+        .iter()
+        .map(|(_, label)| label.len() as u16 + 2)
+        .sum();
+    let mut x = area.x + area.width.saturating_sub(width - 2) / 2;
+    let y = area.bottom().saturating_sub(2);
+
+    for (penalty, label) in buttons {
+        let button = Rect::new(x, y, label.len() as u16, 1).intersection(area);
+        let style = match current == penalty.to_string() {
+            true => Style::default().fg(Color::Black).bg(Color::LightYellow),
+            false => Style::default().fg(Color::LightYellow),
+        };
+        frame.render_widget(Paragraph::new(Span::styled(label, style)), button);
+        app.areas.penalties.push((penalty, button));
+        x += label.len() as u16 + 2;
+    }
+}
+
+fn detail_msg(app: &App, index: usize) -> Paragraph<'static> {
+    let solve = &app.times.times["times"][index];
+    let title = match app.reconstruction_input {
+        Some(_) => format!(
+            "Solve {} reconstruction (Enter twice: save, Esc: cancel)",
+            index + 1
+        ),
+        None => format!("Solve {} (e: edit reconstruction, Esc: close)", index + 1),
+    };
+    let popup_block = Block::default()
+        .title(Span::styled(title, Style::default().fg(Color::LightYellow)))
+        .borders(Borders::ALL);
+
+    if let Some(input) = &app.reconstruction_input {
+        let mut text: Vec<Line> = vec![
+            text::Line::from("One step per line, e.g. y R' F R // cross, times as R@1.25"),
+            text::Line::from(""),
+        ];
+        text.extend(
+            input
+                .split('\n')
+                .map(|line| text::Line::from(line.to_string())),
+        );
+        text.last_mut().unwrap().spans.push(Span::from("_"));
+        return Paragraph::new(text)
+            .block(popup_block)
+            .wrap(Wrap { trim: false });
+    }
+
+    let label =
+        |name: &str| Span::styled(name.to_string(), Style::default().fg(Color::LightYellow));
+    let mut text = vec![
         text::Line::from(vec![
-            Span::styled("f: ", Style::default().fg(Color::LightYellow)),
-            Span::from("Add/remove DNF penalty to last time"),
+            label("Time: "),
+            Span::from(format!(
+                "{:.3} {}",
+                solve["time"].as_f64().unwrap_or(0.0),
+                solve["penalty"].as_str().unwrap_or("")
+            )),
+            label("  ao5: "),
+            Span::from(app.times.display_time(solve["ao5"].as_f64().unwrap_or(0.0))),
+            label("  ao12: "),
+            Span::from(
+                app.times
+                    .display_time(solve["ao12"].as_f64().unwrap_or(0.0)),
+            ),
         ]),
         text::Line::from(vec![
-            Span::styled("2: ", Style::default().fg(Color::LightYellow)),
-            Span::from("Add/remove +2 penalty to last time"),
+            label("Scramble: "),
+            Span::from(solve["scramble"].as_str().unwrap_or("").to_string()),
         ]),
+        text::Line::from(""),
     ];
-    return Paragraph::new(text)
+
+    if let Some(splits) = solve["splits"].as_array() {
+        let splits: Vec<String> = splits
+            .iter()
+            .map(|split| {
+                format!(
+                    "{} {:.3}",
+                    split["phase"].as_str().unwrap_or(""),
+                    split["time"].as_f64().unwrap_or(0.0)
+                )
+            })
+            .collect();
+        text.push(text::Line::from(vec![
+            label("Splits: "),
+            Span::from(splits.join(" | ")),
+        ]));
+        text.push(text::Line::from(""));
+    }
+
+    if let Some(reconstruction) = app.times.reconstruction(index) {
+        let time = solve["time"].as_f64().unwrap_or(0.0);
+        text.push(text::Line::from(vec![
+            label("Moves: "),
+            Span::from(reconstruction.move_count().to_string()),
+            label("  TPS: "),
+            Span::from(format!("{:.2}", reconstruction.tps(time))),
+        ]));
+        for split in reconstruction.splits() {
+            let label_text = match split.label.is_empty() {
+                true => "Unlabelled: ".to_string(),
+                false => format!("{}: ", split.label),
+            };
+            let time = split
+                .time
+                .map(|time| format!(", {:.2}s", time))
+                .unwrap_or_default();
+            text.push(text::Line::from(vec![
+                label(&label_text),
+                Span::from(format!("{} moves{}", split.moves, time)),
+            ]));
+        }
+        text.push(text::Line::from(""));
+    }
+
+    match &app.hints {
+        Some(hints) => {
+            for hint in hints {
+                let solution = match &hint.solution {
+                    Some(moves) => {
+                        let rotation = hint.step.rotation().map(|r| format!("{} ", r));
+                        let moves: Vec<String> = moves.iter().map(|m| m.to_string()).collect();
+                        format!(
+                            "{}{} ({})",
+                            rotation.unwrap_or_default(),
+                            moves.join(" "),
+                            moves.len()
+                        )
+                    }
+                    None => "not found in time".to_string(),
+                };
+                text.push(text::Line::from(vec![
+                    label(&format!("{}: ", hint.step)),
+                    Span::from(solution),
+                ]));
+            }
+        }
+        None if app.searching => text.push(text::Line::from("Searching...")),
+        None => text.push(text::Line::from("No hints for this scramble")),
+    }
+
+    // the last line is left for the penalty buttons
+    Paragraph::new(text)
+        .block(popup_block.padding(Padding::new(0, 0, 0, 1)))
+        .wrap(Wrap { trim: true })
+        .alignment(Alignment::Center)
+}
+
+fn time_entry_msg(input: &str) -> Paragraph<'static> {
+    let popup_block = Block::default()
+        .title(Span::styled(
+            "Type a time (Enter: add, Esc: cancel)",
+            Style::default().fg(Color::LightYellow),
+        ))
+        .borders(Borders::ALL);
+
+    let text = vec![
+        text::Line::from(format!("{}_", input)),
+        text::Line::from(""),
+        text::Line::from("e.g. 1234, 12.34, 1:02.45, 12.34+ or DNF(12.34)"),
+    ];
+    Paragraph::new(text)
+        .block(popup_block)
+        .wrap(Wrap { trim: true })
+        .alignment(Alignment::Center)
+}
+
+fn mbld_msg(mbld: &Mbld) -> Paragraph<'static> {
+    let input = mbld.input.clone().unwrap_or_default();
+    let (title, text) = match mbld.time {
+        None => (
+            "Multi-blind (Enter: scramble, Esc: leave)",
+            vec![
+                text::Line::from(format!("Cubes: {}_", input)),
+                text::Line::from(""),
+                text::Line::from("10 minutes a cube, an hour at most"),
+            ],
+        ),
+        Some(time) => (
+            "Multi-blind (Enter: save, Esc: throw away)",
+            vec![
+                text::Line::from(format!("Solved: {}_", input)),
+                text::Line::from(""),
+                text::Line::from(format!(
+                    "{} of {} cubes, e.g. 5/6, or 5 if all were attempted",
+                    mbld::clock(time),
+                    mbld.cubes()
+                )),
+            ],
+        ),
+    };
+    let popup_block = Block::default()
+        .title(Span::styled(title, Style::default().fg(Color::LightYellow)))
+        .borders(Borders::ALL);
+
+    Paragraph::new(text)
         .block(popup_block)
         .wrap(Wrap { trim: true })
-        .alignment(Alignment::Center);
+        .alignment(Alignment::Center)
+}
+
+// Scramble shown at the top, which sums up the attempt in multi-blind mode
+fn scramble_text(app: &App) -> String {
+    match (app.mode, &app.mbld) {
+        (Mode::Mbld, Some(mbld)) if mbld.cubes() > 0 => format!(
+            "{} cubes, time limit {}",
+            mbld.cubes(),
+            mbld::clock(mbld.time_limit().as_secs_f64())
+        ),
+        (Mode::Mbld, _) => "How many cubes?".to_string(),
+        _ if app.generating => "Generating scramble...".to_string(),
+        _ => app.scramble.to_string(),
+    }
 }
 
 // Render scramble
 fn render_scramble<B: Backend>(app: &mut App, frame: &mut Frame<B>, area: Rect) {
+    let title = match &app.scramble_set {
+        _ if app.mode == Mode::Mbld => format!("Scramble (multi-blind, seed {})", app.seed),
+        _ if app.mode == Mode::Trainer => {
+            let selected = app
+                .trainer
+                .as_ref()
+                .map_or(0, |trainer| trainer.selected.iter().filter(|&&s| s).count());
+            format!("Scramble (trainer, {} cases, seed {})", selected, app.seed)
+        }
+        Some(set) => format!("Scramble ({}, set {})", app.variant, set.progress()),
+        None if app.drills_subset() => {
+            format!(
+                "Scramble ({} {}, seed {})",
+                app.variant, app.subset, app.seed
+            )
+        }
+        None => format!("Scramble ({}, seed {})", app.variant, app.seed),
+    };
     let scramble_block = Block::default()
         .title(Span::styled(
-            "Scramble",
+            title,
             Style::default()
                 .fg(Color::LightYellow)
                 .add_modifier(Modifier::BOLD),
@@ -133,12 +450,13 @@ fn render_scramble<B: Backend>(app: &mut App, frame: &mut Frame<B>, area: Rect)
         .borders(Borders::ALL)
         .border_type(BorderType::Rounded);
 
-    let scramble_p = Paragraph::new(app.scramble.to_string())
+    let scramble_p = Paragraph::new(scramble_text(app))
         .block(scramble_block)
         .alignment(Alignment::Center)
         .wrap(Wrap { trim: true });
 
     frame.render_widget(scramble_p, area);
+    app.areas.scramble = area;
 }
 
 // Render current/best stats
@@ -155,6 +473,14 @@ fn render_basic_stats<B: Backend>(app: &mut App, frame: &mut Frame<B>, area: Rec
         .border_type(BorderType::Rounded);
 
     let stats = match app.show_last_scramble {
+        _ if app.message.is_some() => {
+            vec![text::Line::from(Span::styled(
+                app.message.clone().unwrap(),
+                Style::default().fg(Color::Red),
+            ))]
+        }
+        false if app.mode == Mode::Trainer => trainer_stats(app),
+        false if app.mode == Mode::Mbld => mbld_stats(app),
         true => {
             vec![text::Line::from(vec![
                 Span::styled("Last scramble: ", Style::default().fg(Color::LightYellow)),
@@ -162,25 +488,26 @@ fn render_basic_stats<B: Backend>(app: &mut App, frame: &mut Frame<B>, area: Rec
             ])]
         }
 
-        false => {
-            vec![text::Line::from(vec![
-                Span::styled("Current: ", Style::default().fg(Color::LightYellow)),
-                Span::from("Single: "),
-                Span::from(app.times.currents()[0].to_string()),
-                Span::from(" | ao5: "),
-                Span::from(app.times.currents()[1].to_string()),
-                Span::from(" | ao12: "),
-                Span::from(app.times.currents()[2].to_string()),
-                Span::from("    "),
-                Span::styled("Best: ", Style::default().fg(Color::LightYellow)),
-                Span::from("Single: "),
-                Span::from(app.times.bests()[0].to_string()),
-                Span::from(" | ao5: "),
-                Span::from(app.times.bests()[1].to_string()),
-                Span::from(" | ao12: "),
-                Span::from(app.times.bests()[2].to_string()),
-            ])]
-        }
+        false => vec![text::Line::from(vec![
+            Span::styled("Current: ", Style::default().fg(Color::LightYellow)),
+            Span::from("Single: "),
+            Span::from(app.times.currents()[0].to_string()),
+            Span::from(" | ao5: "),
+            Span::from(app.times.currents()[1].to_string()),
+            Span::from(" | ao12: "),
+            Span::from(app.times.currents()[2].to_string()),
+            Span::from("    "),
+            Span::styled("Best: ", Style::default().fg(Color::LightYellow)),
+            Span::from("Single: "),
+            Span::from(app.times.bests()[0].to_string()),
+            Span::from(" | ao5: "),
+            Span::from(app.times.bests()[1].to_string()),
+            Span::from(" | ao12: "),
+            Span::from(app.times.bests()[2].to_string()),
+        ])]
+        .into_iter()
+        .chain(phase_averages(app))
+        .collect(),
     };
 
     let stats_p = Paragraph::new(stats)
@@ -191,6 +518,112 @@ fn render_basic_stats<B: Backend>(app: &mut App, frame: &mut Frame<B>, area: Rec
     frame.render_widget(stats_p, area);
 }
 
+// Average of each phase, when solves are timed in phases
+fn phase_averages(app: &App) -> Option<Line<'static>> {
+    if app.config.phases.len() < 2 {
+        return None;
+    }
+    let mut spans = vec![Span::styled(
+        "Phase averages: ",
+        Style::default().fg(Color::LightYellow),
+    )];
+    let averages: Vec<String> = app
+        .times
+        .phase_averages()
+        .iter()
+        .map(|(phase, average)| format!("{}: {:.3}", phase, average))
+        .collect();
+    spans.push(Span::from(match averages.is_empty() {
+        true => "NA".to_string(),
+        false => averages.join(" | "),
+    }));
+    Some(text::Line::from(spans))
+}
+
+// Best and last multi-blind attempts
+fn mbld_stats(app: &App) -> Vec<Line<'static>> {
+    let attempts = app.times.mbld_attempts();
+    let Some(last) = attempts.last() else {
+        return vec![text::Line::from(
+            "Scramble the cubes, then hold space to start memorising",
+        )];
+    };
+    let best = app.times.mbld_best();
+    let points = |attempt: &mbld::MbldResult| match (attempt.dnf(), attempt.points()) {
+        (true, _) => String::new(),
+        (false, 1) => " (1 point)".to_string(),
+        (false, points) => format!(" ({} points)", points),
+    };
+    vec![text::Line::from(vec![
+        Span::styled("Last: ", Style::default().fg(Color::LightYellow)),
+        Span::from(format!("{}{}", last, points(last))),
+        Span::from("    "),
+        Span::styled("Best: ", Style::default().fg(Color::LightYellow)),
+        Span::from(match best {
+            Some(best) => format!("{}{}", best, points(&best)),
+            None => "NA".to_string(),
+        }),
+        Span::from("    "),
+        Span::styled("Attempts: ", Style::default().fg(Color::LightYellow)),
+        Span::from(attempts.len().to_string()),
+    ])]
+}
+
+// Stats of the last trained case and its solutions
+fn trainer_stats(app: &App) -> Vec<Line<'static>> {
+    let trainer = app.trainer.as_ref().unwrap();
+    let Some(case) = trainer.last.map(|case| &trainer.cases[case]) else {
+        return vec![text::Line::from("Press space to start the first case")];
+    };
+    let stats = app.times.trainer_stats(&case.name);
+    vec![text::Line::from(vec![
+        Span::styled("Last case: ", Style::default().fg(Color::LightYellow)),
+        Span::from(format!(
+            "{} ({:.3}, avg {:.3}, best {:.3})",
+            case.name,
+            app.time.as_secs_f64(),
+            stats.mean,
+            stats.best
+        )),
+        Span::from("    "),
+        Span::styled("Solution: ", Style::default().fg(Color::LightYellow)),
+        Span::from(case.solutions.join(" | ")),
+    ])]
+}
+
+// Render the list of trainer cases to choose from
+fn render_case_list<B: Backend>(app: &mut App, frame: &mut Frame<B>, area: Rect) {
+    let trainer = app.trainer.as_ref().unwrap();
+    let list_block = Block::default()
+        .title(Span::styled(
+            "Cases (Enter: toggle, a: all, Esc: done)",
+            Style::default().fg(Color::LightYellow),
+        ))
+        .borders(Borders::ALL);
+
+    let rows = trainer.cases.iter().enumerate().map(|(i, case)| {
+        let mark = match trainer.selected[i] {
+            true => "[x]",
+            false => "[ ]",
+        };
+        Row::new(vec![mark.to_string(), case.name.clone()])
+    });
+    let mut state = TableState::default();
+    state.select(Some(trainer.cursor));
+
+    let list = Table::new(rows)
+        .block(list_block)
+        .highlight_style(
+            Style::default()
+                .fg(Color::LightYellow)
+                .add_modifier(Modifier::BOLD),
+        )
+        .highlight_symbol(">>")
+        .widths(&[Constraint::Length(3), Constraint::Percentage(80)]);
+
+    frame.render_stateful_widget(list, area, &mut state);
+}
+
 // Render stats/timer
 fn render_stats_timer<B: Backend>(app: &mut App, frame: &mut Frame<B>, area: Rect) {
     let stats_timer_layout = Layout::default()
@@ -198,8 +631,49 @@ fn render_stats_timer<B: Backend>(app: &mut App, frame: &mut Frame<B>, area: Rec
         .constraints([Constraint::Percentage(40), Constraint::Percentage(60)].as_ref())
         .split(area);
 
-    render_stats_table(app, frame, stats_timer_layout[0]);
-    render_timer(app, frame, stats_timer_layout[1]);
+    match app.mode {
+        Mode::Timer | Mode::Virtual => render_stats_table(app, frame, stats_timer_layout[0]),
+        Mode::Trainer => render_case_table(app, frame, stats_timer_layout[0]),
+        Mode::Mbld => render_mbld(app, frame, stats_timer_layout[0]),
+    }
+    if app.show_net || app.mode == Mode::Virtual {
+        let timer_net_layout = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(50), Constraint::Percentage(50)].as_ref())
+            .split(stats_timer_layout[1]);
+
+        render_timer(app, frame, timer_net_layout[0]);
+        render_net(app, frame, timer_net_layout[1]);
+    } else {
+        render_timer(app, frame, stats_timer_layout[1]);
+    }
+}
+
+// Render net of the scrambled cube
+fn render_net<B: Backend>(app: &mut App, frame: &mut Frame<B>, area: Rect) {
+    let title = match app.mode {
+        Mode::Virtual => "Virtual cube (Esc: leave, Space: new scramble)",
+        _ => "Preview",
+    };
+    let net_block = Block::default()
+        .title(Span::styled(
+            title,
+            Style::default()
+                .fg(Color::LightYellow)
+                .add_modifier(Modifier::BOLD),
+        ))
+        .title_alignment(Alignment::Left)
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded);
+
+    let scrambled = app.scrambled_cube();
+    let cube = match &app.virtual_cube {
+        Some(virtual_cube) => &virtual_cube.cube,
+        None => &scrambled,
+    };
+    let net = Net::new(cube, app.config.colour_scheme).block(net_block);
+
+    frame.render_widget(net, area);
 }
 
 // Render stats table
@@ -223,18 +697,12 @@ fn render_stats_table<B: Backend>(app: &mut App, frame: &mut Frame<B>, area: Rec
         .rev()
         .map(|t| {
             Row::new(vec![
-                Line::from(format!("{:.3}", t["time"].as_f64().unwrap_or(0.0) as f64))
+                Line::from(format!("{:.3}", t["time"].as_f64().unwrap_or(0.0)))
+                    .alignment(Alignment::Center),
+                Line::from(app.times.display_time(t["ao5"].as_f64().unwrap_or(0.0)))
+                    .alignment(Alignment::Center),
+                Line::from(app.times.display_time(t["ao12"].as_f64().unwrap_or(0.0)))
                     .alignment(Alignment::Center),
-                Line::from(
-                    app.times
-                        .display_time(t["ao5"].as_f64().unwrap_or(0.0) as f64),
-                )
-                .alignment(Alignment::Center),
-                Line::from(
-                    app.times
-                        .display_time(t["ao12"].as_f64().unwrap_or(0.0) as f64),
-                )
-                .alignment(Alignment::Center),
             ])
             .bottom_margin(1)
             .style(
@@ -267,16 +735,165 @@ fn render_stats_table<B: Backend>(app: &mut App, frame: &mut Frame<B>, area: Rec
             Constraint::Percentage(33),
         ]);
 
-    frame.render_widget(stats_table, area);
+    frame.render_stateful_widget(stats_table, area, &mut app.table_state);
+
+    // rows start below the border, padding and header
+    let rows = area.inner(&Margin {
+        vertical: 2,
+        horizontal: 2,
+    });
+    app.areas.times = Rect {
+        y: rows.y + 2,
+        height: rows.height.saturating_sub(2),
+        ..rows
+    };
+}
+
+// Render the multi-blind scrambles and past attempts
+fn render_mbld<B: Backend>(app: &mut App, frame: &mut Frame<B>, area: Rect) {
+    let mbld_layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Percentage(60), Constraint::Percentage(40)].as_ref())
+        .split(area);
+    let block = |title: &'static str| {
+        Block::default()
+            .title(Span::styled(
+                title,
+                Style::default()
+                    .fg(Color::LightYellow)
+                    .add_modifier(Modifier::BOLD),
+            ))
+            .title_alignment(Alignment::Left)
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+    };
+
+    let scrambles: Vec<Line> = app
+        .mbld
+        .iter()
+        .flat_map(|mbld| mbld.scrambles.iter().enumerate())
+        .map(|(i, scramble)| text::Line::from(format!("{}. {}", i + 1, scramble)))
+        .collect();
+    let scrambles_p = Paragraph::new(scrambles)
+        .block(block("Scrambles").padding(Padding::new(1, 1, 0, 0)))
+        .wrap(Wrap { trim: true });
+    frame.render_widget(scrambles_p, mbld_layout[0]);
+
+    let rows: Vec<Row> = app
+        .times
+        .mbld_attempts()
+        .iter()
+        .rev()
+        .map(|attempt| {
+            let points = match attempt.dnf() {
+                true => "DNF".to_string(),
+                false => attempt.points().to_string(),
+            };
+            Row::new(vec![
+                format!("{}/{}", attempt.solved, attempt.attempted),
+                points,
+                mbld::clock(attempt.time),
+            ])
+            .style(Style::default().fg(match attempt.dnf() {
+                true => Color::Red,
+                false => Color::White,
+            }))
+        })
+        .collect();
+    let attempts_table = Table::new(rows)
+        .style(Style::default().fg(Color::White))
+        .header(
+            Row::new(vec!["Cubes", "Points", "Time"])
+                .style(Style::default().fg(Color::LightYellow))
+                .bottom_margin(1),
+        )
+        .block(block("Attempts").padding(Padding::new(1, 1, 0, 0)))
+        .column_spacing(2)
+        .widths(&[
+            Constraint::Percentage(33),
+            Constraint::Percentage(33),
+            Constraint::Percentage(33),
+        ]);
+    frame.render_widget(attempts_table, mbld_layout[1]);
+}
+
+// Render per-case trainer stats
+fn render_case_table<B: Backend>(app: &mut App, frame: &mut Frame<B>, area: Rect) {
+    let case_table_block = Block::default()
+        .title(Span::styled(
+            "Cases",
+            Style::default()
+                .fg(Color::LightYellow)
+                .add_modifier(Modifier::BOLD),
+        ))
+        .title_alignment(Alignment::Left)
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .padding(Padding::new(1, 1, 1, 1));
+
+    let trainer = app.trainer.as_ref().unwrap();
+    let now = schedule::now();
+    let rows = trainer
+        .cases
+        .iter()
+        .zip(&trainer.selected)
+        .filter(|(_, &selected)| selected)
+        .map(|(case, _)| {
+            let stats = app.times.trainer_stats(&case.name);
+            let time = |time: f64| match stats.count {
+                0 => "NA".to_string(),
+                _ => format!("{:.3}", time),
+            };
+            Row::new(vec![
+                Line::from(case.name.clone()).alignment(Alignment::Center),
+                Line::from(stats.count.to_string()).alignment(Alignment::Center),
+                Line::from(time(stats.mean)).alignment(Alignment::Center),
+                Line::from(time(stats.best)).alignment(Alignment::Center),
+                Line::from(stats.misses.to_string()).alignment(Alignment::Center),
+                Line::from(match app.times.schedule(&case.name) {
+                    Some(schedule) => schedule.due_in(now),
+                    None => "new".to_string(),
+                })
+                .alignment(Alignment::Center),
+            ])
+        });
+
+    let case_table = Table::new(rows)
+        .style(Style::default().fg(Color::White))
+        .header(
+            Row::new(vec!["Case", "Count", "Avg", "Best", "Misses", "Next"])
+                .style(Style::default().fg(Color::LightYellow))
+                .bottom_margin(1),
+        )
+        .block(case_table_block)
+        .column_spacing(2)
+        .widths(&[
+            Constraint::Percentage(16),
+            Constraint::Percentage(16),
+            Constraint::Percentage(16),
+            Constraint::Percentage(16),
+            Constraint::Percentage(16),
+            Constraint::Percentage(16),
+        ]);
+
+    frame.render_widget(case_table, area);
 }
 
 // Render timer
 fn render_timer<B: Backend>(app: &mut App, frame: &mut Frame<B>, area: Rect) {
+    let (title, title_color) = match (app.current_phase(), app.warning) {
+        (_, Some(warning)) => (format!("Timer ({} seconds!)", warning), Color::LightRed),
+        (Some(phase), _) => (format!("Timer ({})", phase), Color::LightYellow),
+        (None, _) if app.mode == Mode::Timer => {
+            (format!("Timer ({})", app.inspection), Color::LightYellow)
+        }
+        (None, _) => ("Timer".to_string(), Color::LightYellow),
+    };
     let timer_block = Block::default()
         .title(Span::styled(
-            "Timer",
+            title,
             Style::default()
-                .fg(Color::LightYellow)
+                .fg(title_color)
                 .add_modifier(Modifier::BOLD),
         ))
         .title_alignment(Alignment::Left)
@@ -0,0 +1,80 @@
+use crate::cube::Cube;
+use crate::turns::{Direction, Layers, Move, Turn};
+use serde_json::{json, Value};
+use std::time::Duration;
+
+/// Cube turned from the keyboard, with the moves done on it so far.
+#[derive(Debug)]
+pub struct VirtualCube {
+    pub cube: Cube,
+    /// Each move with the time it was done at, from the start of the solve.
+    pub moves: Vec<(Move, Duration)>,
+}
+
+impl VirtualCube {
+    pub fn new(cube: Cube) -> Self {
+        Self {
+            cube,
+            moves: vec![],
+        }
+    }
+
+    pub fn turn(&mut self, m: Move, at: Duration) {
+        self.cube.apply(&m);
+        self.moves.push((m, at));
+    }
+
+    /// Moves of the solve as saved with it: `[{"move": "R", "time": 0.52}, ..]`.
+    pub fn reconstruction(&self) -> Value {
+        let moves: Vec<Value> = self
+            .moves
+            .iter()
+            .map(|(m, at)| json!({ "move": m.to_string(), "time": at.as_secs_f64() }))
+            .collect();
+        Value::from(moves)
+    }
+}
+
+/// Move a key turns, following csTimer's virtual cube keys: `i`/`k` are R/R',
+/// `j`/`f` U/U', `h`/`g` F/F', `s`/`l` D/D', `d`/`e` L/L', `w`/`o` B/B',
+/// with wide turns, slices and rotations around them.
+pub fn key_move(key: char) -> Option<Move> {
+    use Direction::*;
+    use Turn::*;
+    let (direction, turn, layers) = match key {
+        'i' => (Right, Clockwise, Layers::Outer),
+        'k' => (Right, CounterClockwise, Layers::Outer),
+        'j' => (Up, Clockwise, Layers::Outer),
+        'f' => (Up, CounterClockwise, Layers::Outer),
+        'h' => (Front, Clockwise, Layers::Outer),
+        'g' => (Front, CounterClockwise, Layers::Outer),
+        's' => (Down, Clockwise, Layers::Outer),
+        'l' => (Down, CounterClockwise, Layers::Outer),
+        'd' => (Left, Clockwise, Layers::Outer),
+        'e' => (Left, CounterClockwise, Layers::Outer),
+        'w' => (Back, Clockwise, Layers::Outer),
+        'o' => (Back, CounterClockwise, Layers::Outer),
+        'u' => (Right, Clockwise, Layers::Wide(2)),
+        'm' => (Right, CounterClockwise, Layers::Wide(2)),
+        'v' => (Left, Clockwise, Layers::Wide(2)),
+        'r' => (Left, CounterClockwise, Layers::Wide(2)),
+        'c' => (Up, Clockwise, Layers::Wide(2)),
+        ',' => (Up, CounterClockwise, Layers::Wide(2)),
+        'z' => (Down, Clockwise, Layers::Wide(2)),
+        '/' => (Down, CounterClockwise, Layers::Wide(2)),
+        '5' | '6' => (Left, Clockwise, Layers::Slice),
+        'x' | '.' => (Left, CounterClockwise, Layers::Slice),
+        't' | 'y' => (Right, Clockwise, Layers::Rotation),
+        'b' | 'n' => (Right, CounterClockwise, Layers::Rotation),
+        ';' => (Up, Clockwise, Layers::Rotation),
+        'a' => (Up, CounterClockwise, Layers::Rotation),
+        'p' => (Front, Clockwise, Layers::Rotation),
+        'q' => (Front, CounterClockwise, Layers::Rotation),
+        _ => return None,
+    };
+    Some(Move {
+        direction,
+        turn,
+        layers,
+    })
+}
@@ -0,0 +1,134 @@
+use cube_timer::app::Penalty;
+use cube_timer::times::Times;
+
+fn session(times: &[(f64, Penalty)]) -> Times {
+    let mut session = Times::in_memory();
+    for &(time, penalty) in times {
+        session.add_time(time, String::new(), penalty);
+    }
+    session
+}
+
+#[test]
+fn one_dnf_is_the_worst_time() {
+    // the DNF is left out as the worst and 9.0 as the best, whatever their order
+    let times = session(&[
+        (11.0, Penalty::None),
+        (9.0, Penalty::None),
+        (8.0, Penalty::DNF),
+        (12.0, Penalty::None),
+        (13.0, Penalty::None),
+    ]);
+    assert_eq!(times.calc_ao5(4), 12.0);
+
+    let mut twelve = vec![(8.0, Penalty::DNF)];
+    twelve.extend((1..=11).map(|i| (10.0 + i as f64, Penalty::None)));
+    let times = session(&twelve);
+    // 12.0 to 21.0 are left once 11.0 and the DNF are cut
+    assert_eq!(times.calc_ao12(11), 16.5);
+}
+
+#[test]
+fn two_dnfs_make_a_dnf_average() {
+    let times = session(&[
+        (11.0, Penalty::None),
+        (9.0, Penalty::DNF),
+        (10.0, Penalty::None),
+        (12.0, Penalty::DNF),
+        (13.0, Penalty::None),
+    ]);
+    assert_eq!(times.calc_ao5(4), -1.0);
+    assert_eq!(times.display_time(times.calc_ao5(4)), "DNF");
+}
+
+#[test]
+fn averages_without_dnfs_cut_best_and_worst() {
+    let times = session(&[
+        (11.0, Penalty::None),
+        (9.0, Penalty::None),
+        (10.0, Penalty::None),
+        (12.0, Penalty::None),
+        (13.0, Penalty::None),
+    ]);
+    assert_eq!(times.calc_ao5(4), 11.0);
+    assert_eq!(times.calc_ao5(3), 0.0);
+}
+
+#[test]
+fn penalties_update_the_bests() {
+    let mut times = session(&[
+        (12.0, Penalty::None),
+        (11.0, Penalty::None),
+        (10.0, Penalty::None),
+        (13.0, Penalty::None),
+        (14.0, Penalty::None),
+    ]);
+    assert_eq!(times.bests()[0], "10.000");
+    assert_eq!(times.bests()[1], "12.000");
+
+    // a DNF on the best single gives the next best, and the worst is left out
+    times.set_penalty(2, Penalty::DNF);
+    assert_eq!(times.bests()[0], "11.000");
+    assert_eq!(times.bests()[1], "13.000");
+
+    // two DNFs make the only ao5 a DNF
+    times.set_penalty(1, Penalty::DNF);
+    assert_eq!(times.bests()[0], "12.000");
+    assert_eq!(times.bests()[1], "DNF");
+
+    // and toggling the penalties off brings them back
+    times.set_penalty(1, Penalty::None);
+    times.set_penalty(2, Penalty::None);
+    assert_eq!(times.bests()[0], "10.000");
+    assert_eq!(times.bests()[1], "12.000");
+
+    // a +2 is counted in the time
+    times.set_penalty(3, Penalty::PlusTwo);
+    assert_eq!(times.bests()[1], "12.333");
+
+    // and so is a penalty toggled on the last time
+    times.toggle_penalty(Penalty::DNF);
+    assert_eq!(times.bests()[1], "12.667");
+}
+
+#[test]
+fn logged_dnfs_are_never_the_best_average() {
+    // two DNFs logged at the end make the latest ao5 a DNF, not a best
+    let mut times = session(&[
+        (12.0, Penalty::None),
+        (11.0, Penalty::None),
+        (10.0, Penalty::None),
+        (13.0, Penalty::None),
+        (14.0, Penalty::None),
+        (9.0, Penalty::DNF),
+        (8.0, Penalty::DNF),
+    ]);
+    assert_eq!(times.currents()[1], "DNF");
+    assert_eq!(times.bests()[0], "10.000");
+    assert_eq!(times.bests()[1], "12.000");
+
+    // a session of nothing but DNFs has no best single, and a DNF best ao5
+    let times = session(&[(9.0, Penalty::DNF); 5]);
+    assert_eq!(times.bests()[0], "NA");
+    assert_eq!(times.bests()[1], "DNF");
+}
+
+#[test]
+fn deleting_keeps_the_first_average_as_a_best() {
+    let mut times = session(&[
+        (10.0, Penalty::None),
+        (10.0, Penalty::None),
+        (10.0, Penalty::None),
+        (10.0, Penalty::None),
+        (10.0, Penalty::None),
+        (20.0, Penalty::None),
+    ]);
+    times.del_last();
+    assert_eq!(times.bests()[1], "10.000");
+
+    // and deleting the best single gives the next best
+    times.del_time(0);
+    times.add_time(9.0, String::new(), Penalty::None);
+    times.del_last();
+    assert_eq!(times.bests()[0], "10.000");
+}
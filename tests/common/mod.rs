@@ -0,0 +1,34 @@
+//! Apps shared by the integration tests, each a fresh in-memory session with
+//! the same seed so scrambles repeat.
+
+// not every test file uses every fixture
+#![allow(dead_code)]
+
+use cube_timer::app::App;
+use cube_timer::clock::ManualClock;
+use cube_timer::config::Config;
+use cube_timer::times::Times;
+use std::sync::Arc;
+
+/// An app with the default config, timed by the system clock.
+pub fn app() -> App {
+    app_with(Config::default())
+}
+
+/// An app with `config`, timed by the system clock.
+pub fn app_with(config: Config) -> App {
+    App::with_times(1, Times::in_memory(), config)
+}
+
+/// An app with the default config, timed by the returned clock, which only
+/// moves when advanced.
+pub fn clocked_app() -> (App, ManualClock) {
+    let clock = ManualClock::new();
+    let app = App::with_clock(
+        1,
+        Times::in_memory(),
+        Config::default(),
+        Arc::new(clock.clone()),
+    );
+    (app, clock)
+}
@@ -0,0 +1,67 @@
+use cube_timer::cube::Cube;
+use cube_timer::notation::parse_moves;
+use cube_timer::turns::Direction;
+
+fn cube(size: u8, notation: &str) -> Cube {
+    Cube::scrambled(size, &parse_moves(notation).unwrap())
+}
+
+#[test]
+fn r_moves_the_front_right_column_up() {
+    let cube = cube(3, "R");
+    assert!(!cube.is_solved());
+    for row in cube.face(Direction::Up) {
+        assert_eq!(row[2], Direction::Front);
+        assert_eq!(row[0], Direction::Up);
+    }
+    for row in cube.face(Direction::Back) {
+        // the back face is drawn mirrored, so its left column is next to R
+        assert_eq!(row[0], Direction::Up);
+    }
+}
+
+#[test]
+fn u_moves_the_front_row_left() {
+    let cube = cube(3, "U");
+    assert_eq!(cube.face(Direction::Front)[0], vec![Direction::Right; 3]);
+    assert_eq!(cube.face(Direction::Left)[0], vec![Direction::Front; 3]);
+    assert_eq!(cube.face(Direction::Front)[1], vec![Direction::Front; 3]);
+}
+
+#[test]
+fn moves_undone_by_their_inverse() {
+    for notation in ["R R'", "U2 U2", "F B' F' B", "M M'", "x y z z' y' x'"] {
+        assert!(cube(3, notation).is_solved(), "{}", notation);
+    }
+    assert_eq!(
+        cube(3, "R U R' U'"),
+        cube(3, "U R U' R' R U R' U' R U R' U'")
+    );
+}
+
+#[test]
+fn sexy_move_has_order_six() {
+    let sexy = "R U R' U' ".repeat(6);
+    assert!(cube(3, &sexy).is_solved());
+    assert!(!cube(3, &"R U R' U' ".repeat(3)).is_solved());
+}
+
+#[test]
+fn rotations_keep_the_cube_solved() {
+    assert!(cube(3, "x y2 z'").is_solved());
+    assert_eq!(
+        cube(3, "x").face(Direction::Up),
+        vec![vec![Direction::Front; 3]; 3]
+    );
+}
+
+#[test]
+fn slices_and_wide_moves_turn_their_layers() {
+    // a wide move is the face turn and the slice below it, and M follows L
+    assert_eq!(cube(3, "Rw"), cube(3, "R M'"));
+    assert_eq!(cube(3, "x"), cube(3, "R M' L'"));
+    // on 4x4 3Rw leaves only the L face
+    assert_eq!(cube(4, "3Rw L'"), cube(4, "x"));
+    assert!(!cube(4, "Rw").is_solved());
+    assert!(cube(5, "3Rw 3Rw'").is_solved());
+}
@@ -0,0 +1,91 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use cube_timer::app::{App, Penalty};
+use cube_timer::entry::parse_time;
+use cube_timer::handler::handle_key_events;
+use std::time::Instant;
+
+mod common;
+
+fn type_keys(app: &mut App, text: &str) {
+    for c in text.chars() {
+        press(app, KeyCode::Char(c));
+    }
+}
+
+fn press(app: &mut App, code: KeyCode) {
+    let key = KeyEvent::new_with_kind(code, KeyModifiers::NONE, KeyEventKind::Press);
+    handle_key_events(key, Instant::now(), app).unwrap();
+}
+
+#[test]
+fn digits_read_from_the_right() {
+    assert_eq!(parse_time("1234"), Ok((12.34, Penalty::None)));
+    assert_eq!(parse_time("987"), Ok((9.87, Penalty::None)));
+    assert_eq!(parse_time("10245"), Ok((62.45, Penalty::None)));
+    assert_eq!(parse_time("9999"), Ok((99.99, Penalty::None)));
+    assert!(parse_time("17500").is_err());
+}
+
+#[test]
+fn seconds_and_minutes() {
+    assert_eq!(parse_time("12.34"), Ok((12.34, Penalty::None)));
+    assert_eq!(parse_time(" 8.5 "), Ok((8.5, Penalty::None)));
+    assert_eq!(parse_time("1:02.45"), Ok((62.45, Penalty::None)));
+    assert_eq!(parse_time("2:05"), Ok((125.0, Penalty::None)));
+    assert!(parse_time("1:75.00").is_err());
+    assert!(parse_time("1:").is_err());
+    assert!(parse_time("12.3.4").is_err());
+    assert!(parse_time("-12.34").is_err());
+    assert!(parse_time("1e3").is_err());
+    assert!(parse_time("0.00").is_err());
+    assert!(parse_time("").is_err());
+}
+
+#[test]
+fn penalties() {
+    assert_eq!(parse_time("12.34+"), Ok((12.34, Penalty::PlusTwo)));
+    assert_eq!(parse_time("1234+"), Ok((12.34, Penalty::PlusTwo)));
+    assert_eq!(parse_time("DNF(12.3)"), Ok((12.3, Penalty::DNF)));
+    assert_eq!(parse_time("dnf(1:02.45)"), Ok((62.45, Penalty::DNF)));
+    assert!(parse_time("DNF").is_err());
+    assert!(parse_time("DNF(12.3").is_err());
+    assert!(parse_time("12.34++").is_err());
+}
+
+#[test]
+fn typed_times_join_the_session() {
+    let mut app = common::app();
+    let scramble = app.scramble.to_string();
+
+    press(&mut app, KeyCode::Char('m'));
+    type_keys(&mut app, "1:75");
+    press(&mut app, KeyCode::Enter);
+    assert!(app.message.is_some());
+    assert_eq!(app.times.num_times(), 0);
+
+    // the error goes once typing carries on
+    press(&mut app, KeyCode::Backspace);
+    press(&mut app, KeyCode::Backspace);
+    type_keys(&mut app, "02.45+");
+    assert_eq!(app.message, None);
+    press(&mut app, KeyCode::Enter);
+    assert_eq!(app.time_input, None);
+    assert_eq!(app.times.num_times(), 1);
+    let solve = &app.times.times["times"][0];
+    assert_eq!(solve["time"], 64.45);
+    assert_eq!(solve["penalty"], "+2");
+    assert_eq!(solve["scramble"], scramble.as_str());
+    assert_ne!(app.scramble.to_string(), scramble);
+
+    press(&mut app, KeyCode::Char('m'));
+    type_keys(&mut app, "DNF(9.5)");
+    press(&mut app, KeyCode::Enter);
+    assert_eq!(app.times.times["times"][1]["penalty"], "DNF");
+
+    // Esc leaves without adding anything
+    press(&mut app, KeyCode::Char('m'));
+    type_keys(&mut app, "1234");
+    press(&mut app, KeyCode::Esc);
+    assert_eq!(app.time_input, None);
+    assert_eq!(app.times.num_times(), 2);
+}
@@ -0,0 +1,206 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use cube_timer::app::{App, State};
+use cube_timer::clock::SystemClock;
+use cube_timer::config::Config;
+use cube_timer::evdev::{self, encode, Keyboard, EV_KEY, KEY_SPACE};
+use cube_timer::event::Event;
+use cube_timer::handler::{handle_device_events, handle_event, handle_key_events};
+use cube_timer::input::InputMode;
+use cube_timer::inspection::Inspection;
+use std::io::Cursor;
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+mod common;
+
+const EV_SYN: u16 = 0;
+const KEY_A: u16 = 30;
+
+/// A fake device giving the events in order.
+fn device(events: &[(u16, u16, i32)]) -> Cursor<Vec<u8>> {
+    let bytes = events
+        .iter()
+        .flat_map(|&(kind, code, value)| encode(kind, code, value))
+        .collect();
+    Cursor::new(bytes)
+}
+
+#[test]
+fn reads_space_presses_and_releases() {
+    let mut keyboard = Keyboard::new(device(&[
+        (EV_KEY, KEY_SPACE, 1),
+        (EV_SYN, 0, 0),
+        (EV_KEY, KEY_A, 1),
+        (EV_KEY, KEY_SPACE, 2),
+        (EV_KEY, KEY_SPACE, 0),
+        (EV_SYN, 0, 0),
+    ]));
+    let kinds: Vec<KeyEventKind> = std::iter::from_fn(|| keyboard.next_key().unwrap())
+        .map(|key| {
+            assert_eq!(key.code, KeyCode::Char(' '));
+            key.kind
+        })
+        .collect();
+    assert_eq!(
+        kinds,
+        [
+            KeyEventKind::Press,
+            KeyEventKind::Repeat,
+            KeyEventKind::Release
+        ]
+    );
+}
+
+#[test]
+fn truncated_event_ends_the_device() {
+    let mut bytes = device(&[(EV_KEY, KEY_SPACE, 1)]).into_inner();
+    bytes.extend_from_slice(&encode(EV_KEY, KEY_SPACE, 0)[..10]);
+    let mut keyboard = Keyboard::new(Cursor::new(bytes));
+    assert!(keyboard.next_key().unwrap().is_some());
+    assert!(keyboard.next_key().unwrap().is_none());
+}
+
+#[test]
+fn forwards_keys_as_device_events() {
+    let (sender, receiver) = mpsc::channel();
+    let source = device(&[(EV_KEY, KEY_SPACE, 1), (EV_KEY, KEY_SPACE, 0)]);
+    thread::spawn(move || evdev::forward(source, &sender, &SystemClock).unwrap());
+    let kinds: Vec<KeyEventKind> = receiver
+        .iter()
+        .map(|event| match event {
+            Event::Device(key, _) => key.kind,
+            other => panic!("unexpected {:?}", other),
+        })
+        .collect();
+    assert_eq!(kinds, [KeyEventKind::Press, KeyEventKind::Release]);
+}
+
+#[test]
+fn device_space_times_a_solve() {
+    let config = Config {
+        hold: Duration::ZERO,
+        ..Config::default()
+    };
+    let mut app = common::app_with(config);
+    app.inspection = Inspection::None;
+    app.input = InputMode::Evdev;
+
+    let mut keyboard = Keyboard::new(device(&[
+        (EV_KEY, KEY_SPACE, 1),
+        (EV_KEY, KEY_SPACE, 0),
+        (EV_KEY, KEY_SPACE, 1),
+        (EV_KEY, KEY_SPACE, 0),
+    ]));
+    let mut states = vec![];
+    let mut at = Instant::now();
+    while let Some(key) = keyboard.next_key().unwrap() {
+        handle_device_events(key, at, &mut app).unwrap();
+        states.push(app.state);
+        at += Duration::from_millis(500);
+    }
+    assert_eq!(
+        states,
+        [State::Holding, State::Timing, State::Stopped, State::Idle]
+    );
+    assert_eq!(app.times.num_times(), 1);
+    assert_eq!(app.time, Duration::from_millis(500));
+}
+
+/// An app reading space from the keyboard, timing a solve started at `start`.
+fn timing(config: Config, start: Instant) -> App {
+    let mut app = common::app_with(Config {
+        hold: Duration::ZERO,
+        ..config
+    });
+    app.inspection = Inspection::None;
+    app.input = InputMode::Evdev;
+    let mut keyboard = Keyboard::new(device(&[(EV_KEY, KEY_SPACE, 1), (EV_KEY, KEY_SPACE, 0)]));
+    while let Some(key) = keyboard.next_key().unwrap() {
+        handle_device_events(key, start, &mut app).unwrap();
+    }
+    assert_eq!(app.state, State::Timing);
+    app
+}
+
+fn terminal_press(app: &mut App, code: KeyCode, at: Instant) {
+    let key = KeyEvent::new_with_kind(code, KeyModifiers::NONE, KeyEventKind::Press);
+    handle_key_events(key, at, app).unwrap();
+}
+
+#[test]
+fn terminal_space_is_left_to_the_device() {
+    let config = Config {
+        phases: vec!["Cross".to_string(), "F2L".to_string()],
+        ..Config::default()
+    };
+    let start = Instant::now();
+    let mut app = timing(config, start);
+
+    // the same press arrives from the terminal and the keyboard
+    terminal_press(&mut app, KeyCode::Char(' '), start + Duration::from_secs(1));
+    assert!(app.timer.splits.is_empty());
+    let mut keyboard = Keyboard::new(device(&[(EV_KEY, KEY_SPACE, 1)]));
+    let key = keyboard.next_key().unwrap().unwrap();
+    handle_device_events(key, start + Duration::from_secs(2), &mut app).unwrap();
+    assert_eq!(app.timer.splits.len(), 1);
+    assert_eq!(app.state, State::Timing);
+}
+
+#[test]
+fn other_keys_stop_the_timer_without_a_release() {
+    let start = Instant::now();
+    let mut app = timing(Config::default(), start);
+
+    terminal_press(&mut app, KeyCode::Char('x'), start + Duration::from_secs(3));
+    assert_eq!(app.state, State::Idle);
+    assert_eq!(app.times.num_times(), 1);
+    assert_eq!(app.time, Duration::from_secs(3));
+
+    // and keys do what they're bound to again
+    terminal_press(&mut app, KeyCode::Char('h'), start + Duration::from_secs(4));
+    assert!(app.show_help);
+}
+
+#[test]
+fn device_keys_are_ignored_in_other_modes() {
+    let mut app = common::app();
+    app.input = InputMode::Release;
+    let mut keyboard = Keyboard::new(device(&[(EV_KEY, KEY_SPACE, 1)]));
+    let key = keyboard.next_key().unwrap().unwrap();
+    handle_device_events(key, Instant::now(), &mut app).unwrap();
+    assert_eq!(app.state, State::Idle);
+}
+
+#[cfg(not(feature = "evdev"))]
+#[test]
+fn no_keyboards_without_the_feature() {
+    let error = evdev::open_keyboards().unwrap_err();
+    assert_eq!(error.kind(), std::io::ErrorKind::Unsupported);
+}
+
+#[test]
+fn device_keys_are_ignored_out_of_focus() {
+    let mut app = common::app();
+    app.input = InputMode::Evdev;
+    let mut keyboard = Keyboard::new(device(&[(EV_KEY, KEY_SPACE, 1), (EV_KEY, KEY_SPACE, 1)]));
+
+    handle_event(Event::FocusLost, &mut app).unwrap();
+    let key = keyboard.next_key().unwrap().unwrap();
+    handle_device_events(key, Instant::now(), &mut app).unwrap();
+    assert_eq!(app.state, State::Idle);
+
+    handle_event(Event::FocusGained, &mut app).unwrap();
+    let key = keyboard.next_key().unwrap().unwrap();
+    handle_device_events(key, Instant::now(), &mut app).unwrap();
+    assert_eq!(app.state, State::Holding);
+}
+
+#[test]
+fn events_are_the_size_of_an_input_event() {
+    // a timeval of two longs, then the type, code and value
+    let long = std::mem::size_of::<std::ffi::c_long>();
+    assert_eq!(evdev::EVENT_SIZE, 2 * long + 8);
+    #[cfg(target_pointer_width = "64")]
+    assert_eq!(evdev::EVENT_SIZE, 24);
+}
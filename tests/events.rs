@@ -0,0 +1,75 @@
+use cube_timer::app::State;
+use cube_timer::event::{Event, EventHandler};
+use cube_timer::handler::handle_event;
+use cube_timer::inspection::Inspection;
+use std::time::{Duration, Instant};
+
+mod common;
+
+#[test]
+fn paste_goes_into_reconstruction_input() {
+    let mut app = common::app();
+    handle_event(Event::Paste("R U".to_string()), &mut app).unwrap();
+    assert!(app.reconstruction_input.is_none());
+
+    app.reconstruction_input = Some(String::new());
+    handle_event(Event::Paste("R U R'\r\nU' // pair".to_string()), &mut app).unwrap();
+    assert_eq!(
+        app.reconstruction_input.as_deref(),
+        Some("R U R'\nU' // pair")
+    );
+}
+
+#[test]
+fn losing_focus_lets_go_of_a_hold() {
+    let mut app = common::app();
+    app.inspection = Inspection::None;
+    app.press_space(Instant::now());
+    assert_eq!(app.state, State::Holding);
+
+    handle_event(Event::FocusLost, &mut app).unwrap();
+    assert_eq!(app.state, State::Idle);
+    assert!(!app.focused);
+    assert!(app.message.is_none());
+    handle_event(Event::FocusGained, &mut app).unwrap();
+    assert!(app.focused);
+}
+
+#[test]
+fn focus_warning_lasts_until_focus_is_back() {
+    let mut app = common::app();
+    app.inspection = Inspection::None;
+    let start = Instant::now();
+    app.press_space(start);
+    app.release_space(start + app.config.hold);
+    assert_eq!(app.state, State::Timing);
+    handle_event(Event::FocusLost, &mut app).unwrap();
+    assert_eq!(app.state, State::Timing);
+    assert!(app.message.is_some());
+
+    handle_event(Event::FocusGained, &mut app).unwrap();
+    assert_eq!(app.state, State::Timing);
+    assert!(app.message.is_none());
+
+    // other messages are left for the next key press
+    app.message = Some("Saved".to_string());
+    handle_event(Event::FocusGained, &mut app).unwrap();
+    assert_eq!(app.message.as_deref(), Some("Saved"));
+}
+
+#[test]
+fn event_handler_stops_cleanly() {
+    let mut events = EventHandler::new(10);
+    let start = Instant::now();
+    events.stop();
+    assert!(start.elapsed() < Duration::from_secs(1));
+    // stopping twice, then dropping, is fine
+    events.stop();
+}
+
+#[test]
+fn unreadable_terminal_ends_the_app_with_an_error() {
+    let mut app = common::app();
+    let error = handle_event(Event::Error("broken pipe".to_string()), &mut app).unwrap_err();
+    assert!(error.to_string().contains("broken pipe"));
+}
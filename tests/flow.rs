@@ -0,0 +1,95 @@
+use cube_timer::app::{App, Penalty, State};
+use cube_timer::clock::{Clock, ManualClock};
+use cube_timer::inspection::Inspection;
+use std::time::Duration;
+
+mod common;
+
+fn seconds(s: f64) -> Duration {
+    Duration::from_secs_f64(s)
+}
+
+/// Goes through a whole solve with the app ticking along: holding space to
+/// start inspection, inspecting for `inspect` seconds, holding again and
+/// solving for `solve` seconds.
+fn solve(app: &mut App, clock: &ManualClock, inspect: f64, solve: f64) {
+    let hold = app.config.hold;
+
+    app.press_space(clock.now());
+    assert_eq!(app.state, State::Holding);
+    clock.advance(hold);
+    app.tick();
+    assert_eq!(app.state, State::Ready);
+    app.release_space(clock.now());
+    assert_eq!(app.state, State::Inspecting);
+
+    // the hold to start counts towards inspection
+    clock.advance(seconds(inspect) - hold);
+    app.tick();
+    app.press_space(clock.now());
+    clock.advance(hold);
+    app.tick();
+    assert_eq!(app.state, State::Ready);
+    app.release_space(clock.now());
+    assert_eq!(app.state, State::Timing);
+
+    clock.advance(seconds(solve / 2.0));
+    app.tick();
+    assert_eq!(app.time, seconds(solve / 2.0));
+    clock.advance(seconds(solve / 2.0));
+    app.press_space(clock.now());
+    assert_eq!(app.state, State::Stopped);
+    clock.advance(seconds(0.1));
+    app.release_space(clock.now());
+    assert_eq!(app.state, State::Idle);
+    clock.advance(seconds(5.0));
+    app.tick();
+}
+
+#[test]
+fn solves_through_to_stats() {
+    let (mut app, clock) = common::clocked_app();
+    app.inspection = Inspection::Wca;
+
+    solve(&mut app, &clock, 8.0, 10.0);
+    solve(&mut app, &clock, 16.0, 9.0); // +2
+    solve(&mut app, &clock, 12.0, 11.0);
+    solve(&mut app, &clock, 18.0, 8.0); // DNF
+    solve(&mut app, &clock, 14.0, 12.5);
+
+    let times = &app.times.times["times"];
+    let penalties: Vec<&str> = (0..5)
+        .map(|i| times[i]["penalty"].as_str().unwrap())
+        .collect();
+    assert_eq!(penalties, ["", "+2", "", "DNF", ""]);
+    let recorded: Vec<f64> = (0..5).map(|i| times[i]["time"].as_f64().unwrap()).collect();
+    assert_eq!(recorded, [10.0, 11.0, 11.0, 8.0, 12.5]);
+
+    // the DNF is the worst, the best single 10 is dropped, leaving 11, 11 and 12.5
+    let currents = app.times.currents();
+    assert_eq!(currents[0], "12.500");
+    assert_eq!(currents[1], "11.500");
+    assert_eq!(
+        app.times.times["bests"]["single"]["time"].as_f64(),
+        Some(10.0)
+    );
+    assert_eq!(app.penalty, Penalty::None);
+}
+
+#[test]
+fn ticks_move_nothing_without_the_clock() {
+    let (mut app, clock) = common::clocked_app();
+    app.inspection = Inspection::None;
+    app.press_space(clock.now());
+    for _ in 0..100 {
+        app.tick();
+    }
+    assert_eq!(app.state, State::Holding);
+    clock.advance(app.config.hold);
+    app.tick();
+    app.release_space(clock.now());
+    for _ in 0..100 {
+        app.tick();
+    }
+    assert_eq!(app.time, Duration::ZERO);
+}
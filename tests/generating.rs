@@ -0,0 +1,31 @@
+use cube_timer::app::{App, State};
+use cube_timer::config::Config;
+use cube_timer::solver;
+use cube_timer::subset::Subset;
+use cube_timer::times::Times;
+use std::thread;
+use std::time::{Duration, Instant};
+
+// Kept on its own, as the solver's tables are built once per test binary
+#[test]
+fn subset_scramble_waits_for_the_solver() {
+    let mut times = Times::in_memory();
+    times.set_subset(Subset::Pll);
+    let mut app = App::with_times(1, times, Config::default());
+    assert!(app.generating);
+    assert!(app.scramble.moves.is_empty());
+
+    // nothing to solve yet
+    app.press_space(Instant::now());
+    assert_eq!(app.state, State::Idle);
+
+    let start = Instant::now();
+    while !solver::ready() {
+        assert!(start.elapsed() < Duration::from_secs(60));
+        thread::sleep(Duration::from_millis(10));
+    }
+    app.tick();
+    assert!(!app.generating);
+    assert!(!app.scramble.moves.is_empty());
+    assert!(app.case.is_some());
+}
@@ -0,0 +1,46 @@
+use cube_timer::app::{App, Penalty};
+use cube_timer::config::Config;
+use cube_timer::hints::{self, Step};
+use cube_timer::scramble::Variant;
+use cube_timer::times::Times;
+use std::thread;
+use std::time::{Duration, Instant};
+
+fn wait_for_tables() {
+    hints::prepare();
+    let start = Instant::now();
+    while !hints::ready() {
+        assert!(start.elapsed() < Duration::from_secs(60));
+        thread::sleep(Duration::from_millis(10));
+    }
+}
+
+fn detail_of_last(times: Times) -> App {
+    let mut app = App::with_times(1, times, Config::default());
+    app.select_time(true);
+    app.open_detail();
+    app
+}
+
+#[test]
+fn three_by_three_solves_get_hints() {
+    let mut times = Times::in_memory();
+    times.add_time(10.0, "R U R' F2 D' L B2".to_string(), Penalty::None);
+    let mut app = detail_of_last(times);
+    wait_for_tables();
+    app.tick();
+    assert!(!app.searching);
+    let hints = app.hints.unwrap();
+    assert_eq!(hints.len(), Step::ALL.len());
+    assert!(hints.iter().all(|hint| hint.solution.is_some()));
+}
+
+#[test]
+fn other_puzzles_get_no_hints() {
+    let mut times = Times::in_memory();
+    times.set_variant(Variant::FourBld);
+    times.add_time(300.0, "Rw U2 Fw' R D2".to_string(), Penalty::None);
+    let app = detail_of_last(times);
+    assert!(!app.searching);
+    assert!(app.hints.is_none());
+}
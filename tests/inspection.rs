@@ -0,0 +1,153 @@
+use cube_timer::app::{App, Penalty, State};
+use cube_timer::clock::{Clock, ManualClock};
+use cube_timer::inspection::Inspection;
+use std::time::Duration;
+
+mod common;
+
+fn app() -> (App, ManualClock) {
+    let (mut app, clock) = common::clocked_app();
+    app.inspection = Inspection::Wca;
+    (app, clock)
+}
+
+fn millis(ms: u64) -> Duration {
+    Duration::from_millis(ms)
+}
+
+/// Holds space long enough to get ready, then lets go.
+fn space(app: &mut App, clock: &ManualClock) {
+    app.press_space(clock.now());
+    clock.advance(app.config.hold);
+    app.release_space(clock.now());
+}
+
+#[test]
+fn wca_penalty_boundaries() {
+    let wca = Inspection::Wca;
+    assert_eq!(wca.penalty(millis(14_900)), Penalty::None);
+    assert_eq!(wca.penalty(millis(15_000)), Penalty::None);
+    assert_eq!(wca.penalty(millis(15_100)), Penalty::PlusTwo);
+    assert_eq!(wca.penalty(millis(16_900)), Penalty::PlusTwo);
+    assert_eq!(wca.penalty(millis(17_000)), Penalty::PlusTwo);
+    assert_eq!(wca.penalty(millis(17_100)), Penalty::DNF);
+    assert_eq!(Inspection::None.penalty(millis(60_000)), Penalty::None);
+}
+
+#[test]
+fn inspection_step_follows_boundaries() {
+    let (mut app, clock) = app();
+    space(&mut app, &clock);
+    assert_eq!(app.state, State::Inspecting);
+    let mut elapsed = 0;
+    for (until, penalty) in [
+        (14_900, Penalty::None),
+        (15_100, Penalty::PlusTwo),
+        (16_900, Penalty::PlusTwo),
+        (17_100, Penalty::DNF),
+    ] {
+        clock.advance(millis(until - elapsed));
+        elapsed = until;
+        app.tick();
+        assert_eq!(app.penalty, penalty, "after {} ms", elapsed);
+    }
+}
+
+#[test]
+fn overrun_leaves_previous_solve_alone() {
+    let (mut app, clock) = app();
+
+    // a clean solve first
+    space(&mut app, &clock);
+    clock.advance(millis(5_000));
+    space(&mut app, &clock);
+    clock.advance(millis(9_000));
+    space(&mut app, &clock);
+    assert_eq!(app.times.times["times"][0]["penalty"], "");
+    assert_eq!(app.times.times["times"][0]["time"].as_f64(), Some(9.0));
+
+    // then an inspection that runs over, ticking many times
+    space(&mut app, &clock);
+    for _ in 0..30 {
+        clock.advance(millis(100));
+        app.tick();
+        app.time_string();
+    }
+    clock.advance(millis(15_000));
+    app.tick();
+    assert_eq!(app.penalty, Penalty::DNF);
+    assert_eq!(app.times.num_times(), 1);
+    assert_eq!(app.times.times["times"][0]["penalty"], "");
+    assert_eq!(app.times.times["times"][0]["time"].as_f64(), Some(9.0));
+}
+
+#[test]
+fn late_start_penalises_new_solve_once() {
+    let (mut app, clock) = app();
+    space(&mut app, &clock);
+    clock.advance(millis(15_700));
+    for _ in 0..10 {
+        app.tick();
+    }
+    assert_eq!(app.penalty, Penalty::PlusTwo);
+
+    // released 16 s into inspection
+    space(&mut app, &clock);
+    assert_eq!(app.state, State::Timing);
+    clock.advance(millis(10_000));
+    space(&mut app, &clock);
+    assert_eq!(app.times.num_times(), 1);
+    let solve = &app.times.times["times"][0];
+    assert_eq!(solve["penalty"], "+2");
+    assert_eq!(solve["time"].as_f64(), Some(12.0));
+}
+
+#[test]
+fn late_start_after_two_seconds_is_dnf() {
+    let (mut app, clock) = app();
+    space(&mut app, &clock);
+    clock.advance(millis(17_500));
+    space(&mut app, &clock);
+    clock.advance(millis(10_000));
+    space(&mut app, &clock);
+    assert_eq!(app.times.times["times"][0]["penalty"], "DNF");
+    assert_eq!(
+        app.times.times["bests"]["single"]["time"].as_f64(),
+        Some(0.0)
+    );
+}
+
+#[test]
+fn warnings_ask_for_the_bell_once_each() {
+    let (mut app, clock) = app();
+    app.config.inspection_bell = true;
+    space(&mut app, &clock);
+    clock.advance(millis(7_900));
+    app.tick();
+    assert!(!app.bell);
+
+    clock.advance(millis(200));
+    app.tick();
+    assert_eq!(app.warning, Some(8));
+    assert!(app.bell);
+    // rung by the terminal when it next draws
+    app.bell = false;
+    clock.advance(millis(100));
+    app.tick();
+    assert!(!app.bell);
+
+    clock.advance(millis(4_000));
+    app.tick();
+    assert_eq!(app.warning, Some(12));
+    assert!(app.bell);
+}
+
+#[test]
+fn warnings_stay_quiet_without_the_bell() {
+    let (mut app, clock) = app();
+    space(&mut app, &clock);
+    clock.advance(millis(8_100));
+    app.tick();
+    assert_eq!(app.warning, Some(8));
+    assert!(!app.bell);
+}
@@ -0,0 +1,125 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use cube_timer::app::State;
+use cube_timer::config::Config;
+use cube_timer::handler::handle_key_events;
+use cube_timer::inspection::Inspection;
+use cube_timer::keymap::{parse_key, Action, Keymap};
+use serde_json::json;
+use std::time::{Duration, Instant};
+
+mod common;
+
+fn press(code: KeyCode) -> KeyEvent {
+    KeyEvent::new_with_kind(code, KeyModifiers::NONE, KeyEventKind::Press)
+}
+
+#[test]
+fn default_bindings() {
+    let keymap = Keymap::default();
+    assert_eq!(
+        keymap.action(&press(KeyCode::Char('s'))),
+        Some(Action::Scramble)
+    );
+    assert_eq!(
+        keymap.action(&press(KeyCode::Char('S'))),
+        Some(Action::Scramble)
+    );
+    assert_eq!(keymap.action(&press(KeyCode::Esc)), Some(Action::Quit));
+    assert_eq!(keymap.action(&press(KeyCode::Char('z'))), None);
+    let ctrl_s = KeyEvent::new(KeyCode::Char('s'), KeyModifiers::CONTROL);
+    assert_eq!(keymap.action(&ctrl_s), None);
+    assert_eq!(keymap.describe(Action::Quit), "q, Esc");
+}
+
+#[test]
+fn config_overrides_and_round_trips() {
+    let keymap =
+        Keymap::from_value(&json!({ "scramble": ["F5", "PageDown"], "help": ["?"] })).unwrap();
+    assert_eq!(keymap.action(&press(KeyCode::F(5))), Some(Action::Scramble));
+    assert_eq!(keymap.action(&press(KeyCode::Char('s'))), None);
+    assert_eq!(
+        keymap.action(&press(KeyCode::Char('?'))),
+        Some(Action::Help)
+    );
+    assert_eq!(keymap.describe(Action::Scramble), "F5, PageDown");
+    assert_eq!(Keymap::from_value(&keymap.to_value()), Ok(keymap));
+    assert_eq!(
+        Keymap::from_value(&Keymap::default().to_value()),
+        Ok(Keymap::default())
+    );
+}
+
+#[test]
+fn conflicts_are_caught() {
+    // with the default keys of another action
+    let error = Keymap::from_value(&json!({ "help": ["s"] })).unwrap_err();
+    assert_eq!(error, "s is bound to both help and scramble");
+    // between two rebound actions, letters in either case
+    let error = Keymap::from_value(&json!({ "reset": ["F2"], "net": ["f2"] })).unwrap_err();
+    assert_eq!(error, "F2 is bound to both reset and net");
+    let error = Keymap::from_value(&json!({ "dnf": ["D"] })).unwrap_err();
+    assert_eq!(error, "d is bound to both delete_last and dnf");
+    // moving a key off an action first is fine
+    assert!(Keymap::from_value(&json!({ "help": ["s"], "scramble": ["F5"] })).is_ok());
+}
+
+#[test]
+fn bad_bindings_are_caught() {
+    assert!(Keymap::from_value(&json!({ "jump": ["j"] })).is_err());
+    assert!(Keymap::from_value(&json!({ "help": ["Hyper"] })).is_err());
+    assert!(Keymap::from_value(&json!({ "help": "h" })).is_err());
+    assert!(Keymap::from_value(&json!({ "help": ["Space"] })).is_err());
+    assert_eq!(parse_key("F13"), None);
+    assert_eq!(parse_key("Enter"), Some(KeyCode::Enter));
+}
+
+#[test]
+fn config_falls_back_to_default_keys_on_conflict() {
+    let config = Config::from_value(&json!({ "keys": { "help": ["s"] } }));
+    assert_eq!(config.keymap, Keymap::default());
+    assert_eq!(config.errors.len(), 1);
+    let app = common::app_with(config);
+    assert!(app.message.is_some());
+}
+
+#[test]
+fn rebound_keys_run_their_actions() {
+    let config = Config::from_value(&json!({ "keys": { "help": ["F1"] } }));
+    let mut app = common::app_with(config);
+    handle_key_events(press(KeyCode::Char('h')), Instant::now(), &mut app).unwrap();
+    assert!(!app.show_help);
+    handle_key_events(press(KeyCode::F(1)), Instant::now(), &mut app).unwrap();
+    assert!(app.show_help);
+}
+
+#[test]
+fn any_key_stops_the_timer() {
+    let config = Config {
+        hold: Duration::ZERO,
+        ..Config::default()
+    };
+    let mut app = common::app_with(config);
+    app.inspection = Inspection::None;
+    let start = Instant::now();
+    let space = |kind| KeyEvent::new_with_kind(KeyCode::Char(' '), KeyModifiers::NONE, kind);
+    handle_key_events(space(KeyEventKind::Press), start, &mut app).unwrap();
+    handle_key_events(space(KeyEventKind::Release), start, &mut app).unwrap();
+    assert_eq!(app.state, State::Timing);
+
+    // `q` stops rather than quits, and the new scramble key does nothing else
+    let stop = start + Duration::from_secs(7);
+    handle_key_events(press(KeyCode::Char('q')), stop, &mut app).unwrap();
+    assert!(app.running);
+    assert_eq!(app.state, State::Stopped);
+    let scramble = app.scramble.to_string();
+    handle_key_events(press(KeyCode::Char('s')), stop, &mut app).unwrap();
+    assert_eq!(app.scramble.to_string(), scramble);
+    let release = KeyEvent::new_with_kind(
+        KeyCode::Char('q'),
+        KeyModifiers::NONE,
+        KeyEventKind::Release,
+    );
+    handle_key_events(release, stop, &mut app).unwrap();
+    assert_eq!(app.state, State::Idle);
+    assert_eq!(app.times.times["times"][0]["time"].as_f64(), Some(7.0));
+}
@@ -0,0 +1,81 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use cube_timer::config::Config;
+use cube_timer::event::Event;
+use cube_timer::handler::handle_key_events;
+use cube_timer::input::InputMode;
+use cube_timer::inspection::Inspection;
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+mod common;
+
+const SOLVES: usize = 5;
+/// Time the main loop takes to draw before it gets to each event.
+const DRAW: Duration = Duration::from_millis(15);
+
+fn space(kind: KeyEventKind) -> Event {
+    let key = KeyEvent::new_with_kind(KeyCode::Char(' '), KeyModifiers::NONE, kind);
+    Event::Key(key, Instant::now())
+}
+
+/// Sends solves the way the event thread would, returning how long each really
+/// took, from the key starting it being read to the key stopping it.
+fn type_solves(sender: mpsc::Sender<Event>) -> Vec<Duration> {
+    let mut solves = vec![];
+    for i in 0..SOLVES {
+        sender.send(space(KeyEventKind::Press)).unwrap();
+        let start = space(KeyEventKind::Release);
+        sender.send(start.clone()).unwrap();
+        thread::sleep(Duration::from_millis(20 + 10 * i as u64));
+        let stop = space(KeyEventKind::Press);
+        sender.send(stop.clone()).unwrap();
+        sender.send(space(KeyEventKind::Release)).unwrap();
+        match (start, stop) {
+            (Event::Key(_, start), Event::Key(_, stop)) => solves.push(stop - start),
+            _ => unreachable!(),
+        }
+    }
+    solves
+}
+
+#[test]
+fn recorded_times_leave_out_handling_latency() {
+    let config = Config {
+        hold: Duration::ZERO,
+        ..Config::default()
+    };
+    let mut app = common::app_with(config);
+    app.inspection = Inspection::None;
+    app.input = InputMode::Release;
+
+    let (sender, receiver) = mpsc::channel();
+    let typist = thread::spawn(move || type_solves(sender));
+
+    // a main loop that is slow to get to each event
+    let mut handling_latency = Duration::ZERO;
+    for event in receiver {
+        thread::sleep(DRAW);
+        if let Event::Key(key, at) = event {
+            handling_latency = handling_latency.max(at.elapsed());
+            handle_key_events(key, at, &mut app).unwrap();
+        }
+    }
+    let solves = typist.join().unwrap();
+    assert_eq!(app.times.num_times(), SOLVES);
+
+    let added = solves
+        .iter()
+        .enumerate()
+        .map(|(i, real)| {
+            let recorded = app.times.times["times"][i]["time"].as_f64().unwrap();
+            (recorded - real.as_secs_f64()).abs()
+        })
+        .fold(0.0, f64::max);
+    println!(
+        "handled up to {:?} after reading, adding at most {:.6}s to a time",
+        handling_latency, added
+    );
+    assert!(handling_latency >= DRAW);
+    assert!(added < 1e-6, "times are off by up to {}s", added);
+}
@@ -0,0 +1,148 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use cube_timer::app::{App, Mode, State};
+use cube_timer::clock::{Clock, ManualClock};
+use cube_timer::handler::handle_key_events;
+use cube_timer::mbld::{parse_cubes, MbldResult};
+use std::time::Duration;
+
+mod common;
+
+fn minutes(m: u64) -> Duration {
+    Duration::from_secs(m * 60)
+}
+
+fn key(app: &mut App, clock: &ManualClock, code: KeyCode, kind: KeyEventKind) {
+    let key = KeyEvent::new_with_kind(code, KeyModifiers::NONE, kind);
+    handle_key_events(key, clock.now(), app).unwrap();
+}
+
+fn type_keys(app: &mut App, clock: &ManualClock, text: &str) {
+    for c in text.chars() {
+        key(app, clock, KeyCode::Char(c), KeyEventKind::Press);
+    }
+    key(app, clock, KeyCode::Enter, KeyEventKind::Press);
+}
+
+/// Starts an attempt, holding space until the timer is ready.
+fn start(app: &mut App, clock: &ManualClock) {
+    key(app, clock, KeyCode::Char(' '), KeyEventKind::Press);
+    clock.advance(app.config.hold);
+    app.tick();
+    key(app, clock, KeyCode::Char(' '), KeyEventKind::Release);
+    assert_eq!(app.state, State::Timing);
+}
+
+fn mbld_app() -> (App, ManualClock) {
+    let (mut app, clock) = common::clocked_app();
+    key(&mut app, &clock, KeyCode::Char('b'), KeyEventKind::Press);
+    assert_eq!(app.mode, Mode::Mbld);
+    (app, clock)
+}
+
+fn result(solved: usize, attempted: usize, time: f64) -> MbldResult {
+    MbldResult {
+        solved,
+        attempted,
+        time,
+    }
+}
+
+#[test]
+fn points_and_dnfs() {
+    assert_eq!(result(5, 6, 2400.0).points(), 4);
+    assert!(!result(5, 6, 2400.0).dnf());
+    assert_eq!(result(2, 4, 2400.0).points(), 0);
+    assert!(!result(2, 4, 2400.0).dnf());
+    assert!(result(2, 5, 2400.0).dnf());
+    assert!(result(1, 2, 600.0).dnf());
+    assert_eq!(result(5, 6, 2462.7).to_string(), "5/6 41:02");
+    assert_eq!(result(1, 2, 600.0).to_string(), "DNF (1/2 10:00)");
+}
+
+#[test]
+fn ranking() {
+    let ranked = |a: MbldResult, b: MbldResult| a.rank(&b).is_lt();
+    assert!(ranked(result(5, 6, 3000.0), result(3, 3, 900.0)));
+    assert!(ranked(result(3, 3, 900.0), result(4, 5, 900.0)));
+    assert!(ranked(result(3, 3, 900.0), result(3, 3, 901.0)));
+    assert!(ranked(result(2, 4, 3000.0), result(1, 2, 60.0)));
+}
+
+#[test]
+fn counts_typed() {
+    assert_eq!(parse_cubes("6"), Ok(6));
+    assert!(parse_cubes("1").is_err());
+    assert!(parse_cubes("x").is_err());
+    assert_eq!(MbldResult::parse("5/6", 6, 10.0), Ok(result(5, 6, 10.0)));
+    assert_eq!(MbldResult::parse("5", 6, 10.0), Ok(result(5, 6, 10.0)));
+    assert_eq!(MbldResult::parse("3/4", 6, 10.0), Ok(result(3, 4, 10.0)));
+    assert!(MbldResult::parse("5/7", 6, 10.0).is_err());
+    assert!(MbldResult::parse("5/4", 6, 10.0).is_err());
+    assert!(MbldResult::parse("1/1", 6, 10.0).is_err());
+    assert!(MbldResult::parse("", 6, 10.0).is_err());
+}
+
+#[test]
+fn attempt_is_scored_and_kept() {
+    let (mut app, clock) = mbld_app();
+    type_keys(&mut app, &clock, "1");
+    assert!(app.message.is_some());
+    key(&mut app, &clock, KeyCode::Backspace, KeyEventKind::Press);
+    type_keys(&mut app, &clock, "3");
+    let scrambles = app.mbld.as_ref().unwrap().scrambles.clone();
+    assert_eq!(scrambles.len(), 3);
+    assert_eq!(app.mbld.as_ref().unwrap().time_limit(), minutes(30));
+
+    start(&mut app, &clock);
+    clock.advance(minutes(21));
+    app.tick();
+    key(&mut app, &clock, KeyCode::Char(' '), KeyEventKind::Press);
+    assert_eq!(app.state, State::Stopped);
+    assert_eq!(app.time, minutes(21));
+    type_keys(&mut app, &clock, "2/3");
+    assert_eq!(app.state, State::Idle);
+
+    let attempts = &app.times.times["mbld"]["attempts"];
+    assert_eq!(attempts[0]["points"], 1);
+    assert_eq!(attempts[0]["penalty"], "");
+    assert_eq!(
+        attempts[0]["scrambles"][2],
+        scrambles[2].to_string().as_str()
+    );
+    assert_eq!(app.times.mbld_best(), Some(result(2, 3, 1260.0)));
+    assert_eq!(app.times.num_times(), 0);
+
+    // the next attempt has fresh scrambles, and a DNF doesn't beat the best
+    let next = app.mbld.as_ref().unwrap().scrambles[0].to_string();
+    assert_ne!(next, scrambles[0].to_string());
+    start(&mut app, &clock);
+    clock.advance(minutes(5));
+    key(&mut app, &clock, KeyCode::Char(' '), KeyEventKind::Press);
+    type_keys(&mut app, &clock, "1/2");
+    assert_eq!(app.times.mbld_attempts().len(), 2);
+    assert_eq!(app.times.mbld_best(), Some(result(2, 3, 1260.0)));
+
+    key(&mut app, &clock, KeyCode::Char('b'), KeyEventKind::Press);
+    assert_eq!(app.mode, Mode::Timer);
+    assert!(app.mbld.is_none());
+}
+
+#[test]
+fn time_limit_stops_the_attempt() {
+    let (mut app, clock) = mbld_app();
+    type_keys(&mut app, &clock, "2");
+    start(&mut app, &clock);
+    clock.advance(minutes(19));
+    app.tick();
+    assert_eq!(app.state, State::Timing);
+    clock.advance(minutes(2));
+    app.tick();
+    assert_eq!(app.state, State::Stopped);
+    assert_eq!(app.time, minutes(20));
+    assert_eq!(app.mbld.as_ref().unwrap().time, Some(1200.0));
+
+    // thrown away rather than saved
+    key(&mut app, &clock, KeyCode::Esc, KeyEventKind::Press);
+    assert_eq!(app.state, State::Idle);
+    assert!(app.times.mbld_attempts().is_empty());
+}
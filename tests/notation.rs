@@ -0,0 +1,95 @@
+use cube_timer::notation::{parse_moves, simplify, ParseError};
+use cube_timer::turns::{Direction, Layers, Move, Turn};
+
+fn written(notation: &str) -> String {
+    let moves = parse_moves(notation).unwrap();
+    moves
+        .iter()
+        .map(|m| m.to_string())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn simplified(notation: &str) -> String {
+    simplify(&parse_moves(notation).unwrap())
+        .iter()
+        .map(|m| m.to_string())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn error(notation: &str) -> ParseError {
+    parse_moves(notation).unwrap_err()
+}
+
+#[test]
+fn parses_face_turns() {
+    assert_eq!(
+        parse_moves("R U' F2 B2'").unwrap(),
+        vec![
+            Move::new(Direction::Right, Turn::Clockwise),
+            Move::new(Direction::Up, Turn::CounterClockwise),
+            Move::new(Direction::Front, Turn::Double),
+            Move::new(Direction::Back, Turn::Double),
+        ]
+    );
+}
+
+#[test]
+fn parses_wide_moves_slices_and_rotations() {
+    let moves = parse_moves("Rw r 3Fw M E' S2 x y' z2").unwrap();
+    assert_eq!(moves[0].layers, Layers::Wide(2));
+    assert_eq!(moves[1], moves[0]);
+    assert_eq!(moves[2].layers, Layers::Wide(3));
+    assert_eq!(moves[3].layers, Layers::Slice);
+    assert_eq!(moves[6].layers, Layers::Rotation);
+    assert_eq!(
+        written("Rw r 3Fw M E' S2 x y' z2"),
+        "Rw Rw 3Fw M E' S2 x y' z2"
+    );
+}
+
+#[test]
+fn parses_moves_written_together() {
+    assert_eq!(written("RUR'U'"), "R U R' U'");
+    assert_eq!(written("  R2\tU’ \n"), "R2 U'");
+    assert!(parse_moves("").unwrap().is_empty());
+}
+
+#[test]
+fn errors_point_at_the_problem() {
+    assert_eq!(
+        error("R U K"),
+        ParseError {
+            position: 4,
+            message: "unexpected character 'K'".to_string(),
+        }
+    );
+    assert_eq!(error("R3").position, 1);
+    assert_eq!(error("R''").position, 2);
+    assert_eq!(error("R Mw").position, 3);
+    assert_eq!(error("U 3R").position, 2);
+    assert_eq!(error("U 1Rw").position, 2);
+    assert_eq!(error("R 3").position, 3);
+    assert_eq!(
+        error("R 3").to_string(),
+        "expected a move after the layer count at position 3"
+    );
+}
+
+#[test]
+fn simplify_merges_and_cancels() {
+    assert_eq!(simplified("R R"), "R2");
+    assert_eq!(simplified("R R'"), "");
+    assert_eq!(simplified("R2 R"), "R'");
+    assert_eq!(simplified("U R R' U"), "U2");
+    assert_eq!(simplified("R U R'"), "R U R'");
+}
+
+#[test]
+fn simplify_looks_through_the_same_axis() {
+    assert_eq!(simplified("R L R'"), "L");
+    assert_eq!(simplified("R L U R'"), "R L U R'");
+    assert_eq!(simplified("M M' x x"), "x2");
+    assert_eq!(simplified("Rw R Rw'"), "R");
+}
@@ -0,0 +1,115 @@
+use cube_timer::app::{App, Penalty};
+use cube_timer::config::Config;
+use cube_timer::notation::parse_moves;
+use cube_timer::reconstruction::{Reconstruction, Split};
+use cube_timer::times::Times;
+use serde_json::Value;
+
+const SCRAMBLE: &str = "R U R' U' F2";
+
+fn solution() -> Reconstruction {
+    Reconstruction::parse("x x' F2 // cross\nU@1.5 R@2.25 U' // pair\nR'@3.125").unwrap()
+}
+
+#[test]
+fn parse_reads_labels_and_times() {
+    let reconstruction = solution();
+    assert_eq!(reconstruction.moves.len(), 7);
+    assert_eq!(reconstruction.moves[0].step.as_deref(), Some("cross"));
+    assert_eq!(reconstruction.moves[0].time, None);
+    assert_eq!(reconstruction.moves[3].time, Some(1.5));
+    assert_eq!(reconstruction.moves[6].step, None);
+
+    assert_eq!(
+        Reconstruction::parse("R U\nR K").unwrap_err(),
+        "line 2: unexpected character 'K' at position 0"
+    );
+    assert_eq!(
+        Reconstruction::parse("R@x").unwrap_err(),
+        "line 1: bad time in R"
+    );
+}
+
+#[test]
+fn text_reads_back_the_same() {
+    let reconstruction = Reconstruction::parse("R@0.123456 U@1.1 // first\nF").unwrap();
+    let text = reconstruction.to_text();
+    assert_eq!(text, "R@0.123456 U@1.1 // first\nF");
+    assert_eq!(Reconstruction::parse(&text).unwrap(), reconstruction);
+    assert_eq!(
+        Reconstruction::from_value(&reconstruction.to_value()),
+        Some(reconstruction)
+    );
+}
+
+#[test]
+fn validate_checks_the_scramble_is_solved() {
+    let scramble = parse_moves(SCRAMBLE).unwrap();
+    assert_eq!(solution().validate(&scramble, 3), Ok(()));
+    assert!(Reconstruction::parse("F2 U R")
+        .unwrap()
+        .validate(&scramble, 3)
+        .is_err());
+
+    // wide moves on 4x4 aren't the same as on 3x3
+    let scramble = parse_moves("Rw U").unwrap();
+    let undo = Reconstruction::parse("U' Rw'").unwrap();
+    assert_eq!(undo.validate(&scramble, 4), Ok(()));
+    assert_eq!(undo.validate(&scramble, 3), Ok(()));
+    let wrong = Reconstruction::parse("U' R' M").unwrap();
+    assert_eq!(wrong.validate(&scramble, 3), Ok(()));
+    assert!(wrong.validate(&scramble, 4).is_err());
+}
+
+#[test]
+fn splits_and_tps_leave_rotations_out() {
+    let reconstruction = solution();
+    assert_eq!(reconstruction.move_count(), 5);
+    assert_eq!(reconstruction.tps(2.5), 2.0);
+    assert_eq!(reconstruction.tps(0.0), 0.0);
+    assert_eq!(
+        reconstruction.splits(),
+        vec![
+            Split {
+                label: "cross".to_string(),
+                moves: 1,
+                time: None,
+            },
+            Split {
+                label: "pair".to_string(),
+                moves: 3,
+                time: None,
+            },
+            Split {
+                label: String::new(),
+                moves: 1,
+                time: None,
+            },
+        ]
+    );
+
+    let timed = Reconstruction::parse("F2@0.5 // cross\nU@1.5 R@2.25 // pair\nR'@3").unwrap();
+    let times: Vec<Option<f64>> = timed.splits().iter().map(|split| split.time).collect();
+    assert_eq!(times, vec![Some(0.5), Some(1.75), Some(0.75)]);
+}
+
+#[test]
+fn saving_checks_against_the_solve_event() {
+    // a 4x4 blindfolded solve kept with the 3x3 times, as older files have them
+    let mut times = Times::in_memory();
+    times.add_time(200.0, "Rw U".to_string(), Penalty::None);
+    times.times["times"][0]["event"] = Value::from("444bf");
+    let mut app = App::with_times(1, times, Config::default());
+    app.select_time(true);
+    app.open_detail();
+
+    app.reconstruction_input = Some("U' R' M".to_string());
+    app.save_reconstruction();
+    assert!(app.message.take().is_some());
+    assert!(app.times.reconstruction(0).is_none());
+
+    app.reconstruction_input = Some("U' Rw'".to_string());
+    app.save_reconstruction();
+    assert_eq!(app.message, None);
+    assert!(app.times.reconstruction(0).is_some());
+}
@@ -0,0 +1,285 @@
+//! Replays scripted events through the app and checks what is drawn after
+//! each step against the snapshots in `tests/snapshots`. Run with
+//! `UPDATE_SNAPSHOTS=1` to write the snapshots afresh after changing the UI.
+
+mod common;
+
+use crossterm::event::{
+    KeyCode, KeyEvent, KeyEventKind, KeyModifiers, MouseButton, MouseEvent, MouseEventKind,
+};
+use cube_timer::app::{App, Penalty};
+use cube_timer::clock::{Clock, ManualClock};
+use cube_timer::event::Event;
+use cube_timer::handler::handle_event;
+use cube_timer::hints;
+use cube_timer::inspection::Inspection;
+use cube_timer::ui;
+use std::path::PathBuf;
+use std::time::Duration;
+use std::{env, fs, thread};
+use tui::backend::TestBackend;
+use tui::Terminal;
+
+/// An app driven by scripted events on a manual clock, drawn to a test backend.
+struct Replay {
+    name: &'static str,
+    app: App,
+    clock: ManualClock,
+    terminal: Terminal<TestBackend>,
+    /// What was drawn after each step, with the step's label.
+    steps: Vec<(String, String)>,
+}
+
+impl Replay {
+    fn new(name: &'static str, inspection: Inspection) -> Self {
+        // hints wait for their tables, which the manual clock doesn't time
+        hints::prepare();
+        while !hints::ready() {
+            thread::sleep(Duration::from_millis(10));
+        }
+        let (mut app, clock) = common::clocked_app();
+        app.inspection = inspection;
+        Self {
+            name,
+            app,
+            clock,
+            terminal: Terminal::new(TestBackend::new(100, 40)).unwrap(),
+            steps: vec![],
+        }
+    }
+
+    fn send(&mut self, event: Event) {
+        if let Event::Resize(width, height) = event {
+            self.terminal.backend_mut().resize(width, height);
+        }
+        handle_event(event, &mut self.app).unwrap();
+    }
+
+    fn key(&mut self, code: KeyCode, kind: KeyEventKind) {
+        let key = KeyEvent::new_with_kind(code, KeyModifiers::NONE, kind);
+        self.send(Event::Key(key, self.clock.now()));
+    }
+
+    /// Presses and lets go of a key straight away.
+    fn tap(&mut self, c: char) {
+        self.key(KeyCode::Char(c), KeyEventKind::Press);
+        self.key(KeyCode::Char(c), KeyEventKind::Release);
+    }
+
+    /// Clicks or scrolls at a cell of the screen.
+    fn mouse(&mut self, kind: MouseEventKind, column: u16, row: u16) {
+        self.send(Event::Mouse(MouseEvent {
+            kind,
+            column,
+            row,
+            modifiers: KeyModifiers::NONE,
+        }));
+    }
+
+    /// Clicks the penalty button of the detail popup, as last drawn.
+    fn click_penalty(&mut self, penalty: Penalty) {
+        let penalties = &self.app.areas.penalties;
+        let &(_, button) = penalties.iter().find(|(p, _)| *p == penalty).unwrap();
+        self.mouse(MouseEventKind::Down(MouseButton::Left), button.x, button.y);
+    }
+
+    /// Lets `ms` milliseconds pass, ticking once at the end.
+    fn wait(&mut self, ms: u64) {
+        self.clock.advance(Duration::from_millis(ms));
+        self.send(Event::Tick);
+    }
+
+    /// Holds space until the timer is ready, then lets go.
+    fn hold_space(&mut self) {
+        self.key(KeyCode::Char(' '), KeyEventKind::Press);
+        self.wait(self.app.config.hold.as_millis() as u64);
+        self.key(KeyCode::Char(' '), KeyEventKind::Release);
+    }
+
+    /// Times a solve of `ms` milliseconds, without inspection.
+    fn solve(&mut self, ms: u64) {
+        self.hold_space();
+        self.wait(ms);
+        self.tap(' ');
+    }
+
+    /// Draws the app and records what was drawn.
+    fn step(&mut self, label: &str) {
+        let app = &mut self.app;
+        self.terminal.draw(|frame| ui::render(app, frame)).unwrap();
+        let buffer = self.terminal.backend().buffer();
+        let width = buffer.area.width as usize;
+        let lines: Vec<String> = buffer
+            .content
+            .chunks(width)
+            .map(|row| {
+                let line: String = row.iter().map(|cell| cell.symbol.as_str()).collect();
+                line.trim_end().to_string()
+            })
+            .collect();
+        self.steps.push((label.to_string(), lines.join("\n")));
+    }
+
+    /// Checks the steps against the snapshot, or writes it with `UPDATE_SNAPSHOTS`.
+    fn check(self) {
+        let text: String = self
+            .steps
+            .iter()
+            .map(|(label, drawn)| format!("== {} ==\n{}\n", label, drawn))
+            .collect();
+        let path: PathBuf = [env!("CARGO_MANIFEST_DIR"), "tests", "snapshots"]
+            .iter()
+            .collect::<PathBuf>()
+            .join(format!("{}.txt", self.name));
+        if env::var_os("UPDATE_SNAPSHOTS").is_some() {
+            fs::write(&path, text).unwrap();
+            return;
+        }
+        let expected = fs::read_to_string(&path).unwrap_or_else(|_| {
+            panic!(
+                "no snapshot at {}, run with UPDATE_SNAPSHOTS=1",
+                path.display()
+            )
+        });
+        for ((label, drawn), expected) in self.steps.iter().zip(expected.split("== ").skip(1)) {
+            let expected = expected.split_once(" ==\n").unwrap();
+            assert_eq!(label, expected.0, "steps of {} changed", self.name);
+            assert_eq!(
+                format!("{}\n", drawn),
+                expected.1,
+                "{} drew something else after \"{}\"",
+                self.name,
+                label
+            );
+        }
+        assert_eq!(text, expected, "steps of {} changed", self.name);
+    }
+}
+
+#[test]
+fn help_popup() {
+    let mut replay = Replay::new("help_popup", Inspection::None);
+    replay.step("start");
+    replay.tap('h');
+    replay.step("help shown");
+    replay.tap('h');
+    replay.step("help hidden");
+    replay.check();
+}
+
+#[test]
+fn last_scramble() {
+    let mut replay = Replay::new("last_scramble", Inspection::None);
+    replay.solve(9_870);
+    replay.step("after a solve");
+    replay.tap('l');
+    replay.step("last scramble shown");
+    replay.tap('l');
+    replay.step("last scramble hidden");
+    replay.check();
+}
+
+#[test]
+fn penalties() {
+    let mut replay = Replay::new("penalties", Inspection::Wca);
+    replay.hold_space();
+    replay.wait(3_200);
+    replay.step("inspecting");
+    replay.wait(13_000);
+    replay.step("inspection over");
+    replay.hold_space();
+    replay.wait(12_340);
+    replay.step("timing");
+    replay.tap(' ');
+    replay.step("solved with +2");
+    replay.tap('2');
+    replay.step("+2 taken off");
+    replay.tap('f');
+    replay.step("DNF");
+    replay.tap('f');
+    replay.step("DNF taken off");
+    replay.check();
+}
+
+#[test]
+fn times_table() {
+    let mut replay = Replay::new("times_table", Inspection::None);
+    for ms in [10_120, 9_480, 11_030, 8_760, 12_500, 9_990] {
+        replay.solve(ms);
+    }
+    replay.step("six solves");
+    replay.key(KeyCode::Down, KeyEventKind::Press);
+    replay.key(KeyCode::Down, KeyEventKind::Press);
+    replay.step("second newest solve selected");
+    replay.send(Event::Resize(80, 30));
+    replay.step("resized");
+    replay.check();
+}
+
+#[test]
+fn mouse() {
+    let mut replay = Replay::new("mouse", Inspection::None);
+    for ms in [10_120, 9_480, 11_030, 8_760, 12_500, 9_990] {
+        replay.solve(ms);
+    }
+    replay.step("six solves");
+    let table = replay.app.areas.times;
+    replay.mouse(
+        MouseEventKind::Down(MouseButton::Left),
+        table.x + 3,
+        table.y + 2,
+    );
+    replay.step("second newest solve clicked");
+    replay.click_penalty(Penalty::PlusTwo);
+    replay.step("+2 clicked");
+    replay.click_penalty(Penalty::DNF);
+    replay.step("DNF clicked");
+    replay.click_penalty(Penalty::None);
+    replay.step("OK clicked");
+    assert_eq!(replay.app.times.times["times"][4]["time"], 12.5);
+    replay.key(KeyCode::Esc, KeyEventKind::Press);
+    replay.mouse(MouseEventKind::ScrollDown, table.x + 3, table.y);
+    replay.mouse(MouseEventKind::ScrollDown, table.x + 3, table.y);
+    replay.mouse(MouseEventKind::ScrollDown, table.x + 3, table.y);
+    replay.mouse(MouseEventKind::ScrollUp, table.x + 3, table.y);
+    replay.step("scrolled to the fourth newest solve");
+    let scramble = replay.app.areas.scramble;
+    replay.mouse(
+        MouseEventKind::Down(MouseButton::Left),
+        scramble.x + 5,
+        scramble.y + 1,
+    );
+    replay.step("scramble clicked");
+    replay.check();
+}
+
+#[test]
+fn typed_time() {
+    let mut replay = Replay::new("typed_time", Inspection::None);
+    replay.tap('m');
+    for c in "12.34+".chars() {
+        replay.tap(c);
+    }
+    replay.step("time typed");
+    replay.key(KeyCode::Enter, KeyEventKind::Press);
+    replay.step("time added");
+    replay.check();
+}
+
+#[test]
+fn multi_blind() {
+    let mut replay = Replay::new("multi_blind", Inspection::None);
+    replay.tap('b');
+    replay.tap('3');
+    replay.step("cubes typed");
+    replay.key(KeyCode::Enter, KeyEventKind::Press);
+    replay.step("scrambled");
+    replay.solve(1_262_000);
+    replay.step("attempt over");
+    for c in "2/3".chars() {
+        replay.tap(c);
+    }
+    replay.key(KeyCode::Enter, KeyEventKind::Press);
+    replay.step("attempt saved");
+    replay.check();
+}
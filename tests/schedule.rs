@@ -0,0 +1,139 @@
+use cube_timer::app::{App, Mode, State};
+use cube_timer::config::Config;
+use cube_timer::schedule::{self, Schedule, FORGOTTEN};
+use cube_timer::times::Times;
+use cube_timer::trainer::Trainer;
+use serde_json::json;
+use std::time::{Duration, Instant};
+
+const DAY: u64 = 24 * 60 * 60;
+const NOW: u64 = 1_700_000_000;
+
+#[test]
+fn recalled_cases_space_out() {
+    let first = Schedule::default().review(5, NOW);
+    assert_eq!(
+        (first.interval, first.repetitions, first.due),
+        (1, 1, NOW + DAY)
+    );
+    assert!((first.ease - 2.6).abs() < 1e-9);
+
+    let second = first.review(4, NOW + DAY);
+    assert_eq!((second.interval, second.repetitions), (6, 2));
+    assert!((second.ease - 2.6).abs() < 1e-9);
+
+    let third = second.review(3, NOW + 7 * DAY);
+    assert_eq!(third.interval, 16);
+    assert_eq!(third.due, NOW + 23 * DAY);
+    assert!((third.ease - 2.46).abs() < 1e-9);
+}
+
+#[test]
+fn forgotten_cases_start_over() {
+    let learnt = Schedule {
+        ease: 2.5,
+        interval: 15,
+        repetitions: 3,
+        due: NOW,
+    };
+    let forgotten = learnt.review(FORGOTTEN, NOW);
+    assert_eq!((forgotten.interval, forgotten.repetitions), (1, 0));
+    assert!((forgotten.ease - 1.96).abs() < 1e-9);
+
+    // the ease never drops below 1.3
+    let hard = (0..10).fold(learnt, |s, _| s.review(0, NOW));
+    assert!((hard.ease - 1.3).abs() < 1e-9);
+}
+
+#[test]
+fn grade_compares_with_the_average() {
+    assert_eq!(schedule::grade(2.0, None), 4);
+    assert_eq!(schedule::grade(2.0, Some(2.0)), 5);
+    assert_eq!(schedule::grade(3.0, Some(2.0)), 4);
+    assert_eq!(schedule::grade(3.1, Some(2.0)), 3);
+}
+
+#[test]
+fn due_in_rounds_up() {
+    let schedule = Schedule {
+        due: NOW + DAY + 1,
+        ..Schedule::default()
+    };
+    assert_eq!(schedule.due_in(NOW), "2d");
+    assert_eq!(schedule.due_in(NOW + 2), "24h");
+    assert_eq!(schedule.due_in(NOW + DAY), "1h");
+    assert_eq!(schedule.due_in(NOW + DAY + 1), "due");
+    assert_eq!(schedule.due_in(NOW + 2 * DAY), "due");
+    assert!(schedule.is_due(NOW + DAY + 1));
+    assert!(!schedule.is_due(NOW + DAY));
+}
+
+fn trainer_app(times: Times) -> App {
+    let mut app = App::with_times(1, times, Config::default());
+    let cases = json!({ "cases": [{ "name": "T", "setup": "R U R' U'" }] });
+    let mut trainer = Trainer::from_json(&cases).unwrap();
+    trainer.current = Some(0);
+    app.trainer = Some(trainer);
+    app.mode = Mode::Trainer;
+    app
+}
+
+fn attempt(app: &mut App) {
+    let start = Instant::now();
+    app.timer.start_at(start);
+    app.state = State::Timing;
+    app.press_space(start + Duration::from_secs(2));
+}
+
+#[test]
+fn new_and_due_cases_are_reviewed() {
+    let mut app = trainer_app(Times::in_memory());
+    attempt(&mut app);
+    let reviewed = app.times.schedule("T").unwrap();
+    assert_eq!(reviewed.repetitions, 1);
+
+    let mut times = Times::in_memory();
+    let due = Schedule {
+        repetitions: 1,
+        interval: 1,
+        due: schedule::now() - 1,
+        ..Schedule::default()
+    };
+    times.set_schedule("T", due);
+    let mut app = trainer_app(times);
+    attempt(&mut app);
+    assert_eq!(app.times.schedule("T").unwrap().repetitions, 2);
+}
+
+#[test]
+fn practice_before_a_case_is_due_leaves_its_schedule() {
+    let mut times = Times::in_memory();
+    let scheduled = Schedule {
+        repetitions: 2,
+        interval: 6,
+        due: schedule::now() + DAY,
+        ..Schedule::default()
+    };
+    times.set_schedule("T", scheduled);
+    let mut app = trainer_app(times);
+    attempt(&mut app);
+    assert_eq!(app.times.schedule("T"), Some(scheduled));
+    assert_eq!(app.times.trainer_stats("T").count, 1);
+
+    // not recognising it is counted, but doesn't start it over
+    app.record_miss();
+    assert_eq!(app.times.schedule("T"), Some(scheduled));
+    assert_eq!(app.times.trainer_stats("T").misses, 1);
+}
+
+#[test]
+fn misses_grade_a_review_again_once() {
+    let mut app = trainer_app(Times::in_memory());
+    attempt(&mut app);
+    assert!(app.times.schedule("T").unwrap().repetitions > 0);
+
+    app.record_miss();
+    app.record_miss();
+    assert_eq!(app.times.schedule("T").unwrap().repetitions, 0);
+    assert_eq!(app.times.trainer_stats("T").misses, 1);
+}
@@ -0,0 +1,32 @@
+use cube_timer::app::App;
+use cube_timer::config::Config;
+use cube_timer::times::Times;
+
+fn scrambles(seed: u64) -> Vec<String> {
+    let mut app = App::with_times(seed, Times::in_memory(), Config::default());
+    let mut scrambles = vec![];
+    for _ in 0..3 {
+        scrambles.push(app.scramble.to_string().trim_end().to_string());
+        app.new_scramble();
+    }
+    scrambles
+}
+
+#[test]
+fn seed_gives_the_same_scrambles() {
+    assert_eq!(scrambles(42), scrambles(42));
+    assert_ne!(scrambles(42), scrambles(43));
+}
+
+#[test]
+fn seed_scrambles_are_pinned() {
+    // a shared seed has to give the same scrambles on every platform and build
+    assert_eq!(
+        scrambles(42),
+        vec![
+            "D2 L2 F2 B2 D' R2 L D2 U2 L2 U' F2 L' R2 F D' L B' R2 F",
+            "F L D2 U L2 D2 R2 U' L F' R B' F' R2 U B' R2 D2 L' D2",
+            "L U F' R2 L F' B' D' R L' F2 D R2 F2 U' F D2 B2 F2 D",
+        ]
+    );
+}
@@ -0,0 +1,90 @@
+use cube_timer::cube::Cube;
+use cube_timer::cubie::*;
+use cube_timer::notation::parse_moves;
+use cube_timer::solver;
+use cube_timer::subset::Subset;
+use cube_timer::turns::Move;
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+
+fn apply(state: &CubieCube, moves: &[Move]) -> CubieCube {
+    state.multiply(&CubieCube::from_moves(moves).unwrap())
+}
+
+#[test]
+fn cubie_model_matches_the_sticker_cube() {
+    let moves = parse_moves("R U F' D2 L B' R2 U' F2").unwrap();
+    let cube = Cube::scrambled(3, &moves);
+    assert_eq!(CubieCube::from_cube(&cube), CubieCube::from_moves(&moves));
+    assert_eq!(
+        apply(&CubieCube::from_moves(&moves).unwrap(), &invert(&moves)),
+        CubieCube::SOLVED
+    );
+}
+
+#[test]
+fn solutions_solve_random_states() {
+    let mut rng = ChaCha8Rng::seed_from_u64(7);
+    let corners: Vec<usize> = (0..8).collect();
+    let edges: Vec<usize> = (0..12).collect();
+    for _ in 0..10 {
+        let state = CubieCube::random_with(&mut rng, &corners, true, &edges, true);
+        assert!(state.is_solvable());
+        let solution = solver::solve(&state, 24, solver::BUDGET).unwrap();
+        assert!(solution.len() <= 24);
+        assert_eq!(apply(&state, &solution), CubieCube::SOLVED);
+    }
+}
+
+#[test]
+fn unsolvable_states_have_no_solution() {
+    let mut twisted = CubieCube::SOLVED;
+    twisted.co[URF] = 1;
+    assert!(!twisted.is_solvable());
+    assert_eq!(solver::solve(&twisted, 24, solver::BUDGET), None);
+
+    let mut swapped = CubieCube::SOLVED;
+    swapped.ep.swap(UR, UF);
+    assert_eq!(solver::solve(&swapped, 24, solver::BUDGET), None);
+}
+
+#[test]
+fn random_states_with_few_pieces_keep_parity() {
+    let mut rng = ChaCha8Rng::seed_from_u64(3);
+    let corners: Vec<usize> = (0..8).collect();
+    for _ in 0..20 {
+        // too few edges to fix the parity with, so corners are swapped instead
+        let state = CubieCube::random_with(&mut rng, &corners, false, &[DF], false);
+        assert!(state.is_solvable());
+        let state = CubieCube::random_with(&mut rng, &[URF], false, &[UR, UF, UL], false);
+        assert!(state.is_solvable());
+    }
+}
+
+#[test]
+fn subset_scrambles_reach_their_subset() {
+    let mut rng = ChaCha8Rng::seed_from_u64(1);
+    for subset in Subset::ALL {
+        let (scramble, case) = subset.scramble(&mut rng).unwrap();
+        let state = CubieCube::from_moves(&scramble.moves).unwrap();
+        match subset {
+            Subset::Oll | Subset::Pll | Subset::Coll | Subset::Zbll => {
+                // only the yellow D layer is scrambled
+                for corner in [URF, UFL, ULB, UBR] {
+                    assert_eq!((state.cp[corner], state.co[corner]), (corner as u8, 0));
+                }
+                for edge in [UR, UF, UL, UB, FR, FL, BL, BR] {
+                    assert_eq!((state.ep[edge], state.eo[edge]), (edge as u8, 0));
+                }
+                assert_eq!(case, subset.case(&state));
+                assert!(case.is_some());
+            }
+            Subset::EoSolved => assert_eq!(state.eo, [0; 12]),
+            _ => assert_eq!(case, None),
+        }
+        if subset == Subset::Pll {
+            assert_eq!(state.co, [0; 8]);
+            assert_eq!(state.eo, [0; 12]);
+        }
+    }
+}
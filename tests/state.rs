@@ -0,0 +1,229 @@
+use cube_timer::app::{App, State};
+use cube_timer::config::Config;
+use cube_timer::input::{InputMode, RepeatTracker};
+use cube_timer::inspection::Inspection;
+use std::time::{Duration, Instant};
+
+mod common;
+
+fn app(inspection: Inspection) -> App {
+    let config = Config {
+        hold: Duration::from_millis(300),
+        ..Config::default()
+    };
+    let mut app = common::app_with(config);
+    app.inspection = inspection;
+    app
+}
+
+fn after(start: Instant, ms: u64) -> Instant {
+    start + Duration::from_millis(ms)
+}
+
+#[test]
+fn tap_does_not_start() {
+    let mut app = app(Inspection::None);
+    let start = Instant::now();
+    app.press_space(start);
+    assert_eq!(app.state, State::Holding);
+    app.release_space(after(start, 100));
+    assert_eq!(app.state, State::Idle);
+    assert!(!app.timer.running);
+}
+
+#[test]
+fn hold_turns_ready_then_release_starts() {
+    let mut app = app(Inspection::None);
+    let start = Instant::now();
+    app.press_space(start);
+    app.hold_step(after(start, 299));
+    assert_eq!(app.state, State::Holding);
+    app.hold_step(after(start, 300));
+    assert_eq!(app.state, State::Ready);
+
+    // key repeats while held change nothing
+    app.press_space(after(start, 400));
+    assert_eq!(app.state, State::Ready);
+
+    app.release_space(after(start, 500));
+    assert_eq!(app.state, State::Timing);
+    assert!(app.timer.running);
+}
+
+#[test]
+fn release_after_hold_starts_without_tick() {
+    let mut app = app(Inspection::None);
+    let start = Instant::now();
+    app.press_space(start);
+    app.release_space(after(start, 350));
+    assert_eq!(app.state, State::Timing);
+}
+
+#[test]
+fn stop_waits_for_release() {
+    let mut app = app(Inspection::None);
+    let start = Instant::now();
+    app.press_space(start);
+    app.release_space(after(start, 300));
+    app.press_space(after(start, 5_000));
+    assert_eq!(app.state, State::Stopped);
+    assert_eq!(app.times.num_times(), 1);
+
+    // holding on after stopping doesn't get ready for the next solve
+    app.hold_step(after(start, 6_000));
+    assert_eq!(app.state, State::Stopped);
+    app.release_space(after(start, 6_000));
+    assert_eq!(app.state, State::Idle);
+    assert_eq!(app.times.num_times(), 1);
+}
+
+#[test]
+fn inspection_needs_hold_to_start_and_to_end() {
+    let mut app = app(Inspection::Wca);
+    let start = Instant::now();
+
+    // a tap doesn't start inspection
+    app.press_space(start);
+    app.release_space(after(start, 50));
+    assert_eq!(app.state, State::Idle);
+    assert!(!app.inspection_timer.running);
+
+    app.press_space(after(start, 1_000));
+    app.release_space(after(start, 1_300));
+    assert_eq!(app.state, State::Inspecting);
+
+    // an early release keeps inspecting
+    app.press_space(after(start, 2_000));
+    app.release_space(after(start, 2_100));
+    assert_eq!(app.state, State::Inspecting);
+    assert!(app.inspection_timer.running);
+
+    app.press_space(after(start, 3_000));
+    app.hold_step(after(start, 3_300));
+    assert_eq!(app.state, State::Ready);
+    assert!(app.inspection_timer.running);
+    app.release_space(after(start, 3_500));
+    assert_eq!(app.state, State::Timing);
+    assert!(!app.inspection_timer.running);
+}
+
+#[test]
+fn phases_split_without_stopping() {
+    let config = Config {
+        phases: vec!["Cross".to_string(), "F2L".to_string()],
+        ..Config::default()
+    };
+    let mut app = common::app_with(config);
+    app.inspection = Inspection::None;
+    let start = Instant::now();
+    app.press_space(start);
+    app.release_space(after(start, 300));
+
+    app.press_space(after(start, 2_000));
+    assert_eq!(app.state, State::Timing);
+    app.release_space(after(start, 2_100));
+    assert_eq!(app.state, State::Timing);
+
+    app.press_space(after(start, 4_000));
+    assert_eq!(app.state, State::Stopped);
+    assert!(app.times.times["times"][0]["splits"].is_array());
+}
+
+#[test]
+fn arm_mode_presses_to_ready_and_start() {
+    let mut app = app(Inspection::None);
+    app.input = InputMode::Arm;
+    let start = Instant::now();
+    app.arm_space(start);
+    assert_eq!(app.state, State::Ready);
+    app.input_step(after(start, 5_000));
+    assert_eq!(app.state, State::Ready);
+    app.arm_space(after(start, 5_000));
+    assert_eq!(app.state, State::Timing);
+    app.arm_space(after(start, 9_000));
+    assert_eq!(app.state, State::Idle);
+    assert_eq!(app.times.num_times(), 1);
+}
+
+#[test]
+fn repeat_mode_tap_does_not_start() {
+    let mut app = app(Inspection::None);
+    app.input = InputMode::Repeat;
+    let start = Instant::now();
+    app.repeat_space(start);
+    assert_eq!(app.state, State::Holding);
+    app.input_step(after(start, 500));
+    assert_eq!(app.state, State::Holding);
+    app.input_step(after(start, 800));
+    assert_eq!(app.state, State::Idle);
+}
+
+#[test]
+fn repeat_mode_hold_starts_when_repeats_stop() {
+    let mut app = app(Inspection::None);
+    app.input = InputMode::Repeat;
+    let start = Instant::now();
+
+    // first repeat after 500 ms, then every 30 ms
+    app.repeat_space(start);
+    app.repeat_space(after(start, 500));
+    assert_eq!(app.state, State::Ready);
+    for ms in (530..1_000).step_by(30) {
+        app.repeat_space(after(start, ms));
+        app.input_step(after(start, ms + 10));
+        assert_eq!(app.state, State::Ready);
+    }
+    app.input_step(after(start, 1_200));
+    assert_eq!(app.state, State::Timing);
+
+    // stopping, with the key held down, doesn't start again
+    app.repeat_space(after(start, 5_000));
+    assert_eq!(app.state, State::Stopped);
+    app.repeat_space(after(start, 5_500));
+    app.repeat_space(after(start, 5_530));
+    app.input_step(after(start, 5_800));
+    assert_eq!(app.state, State::Idle);
+    assert_eq!(app.times.num_times(), 1);
+}
+
+#[test]
+fn repeat_tracker_waits_longer_for_first_repeat() {
+    let mut tracker = RepeatTracker::default();
+    let start = Instant::now();
+    assert!(tracker.press(start));
+    assert_eq!(tracker.released(after(start, 600)), None);
+    assert!(!tracker.press(after(start, 600)));
+    assert_eq!(tracker.released(after(start, 700)), None);
+    assert_eq!(tracker.released(after(start, 800)), Some(after(start, 600)));
+    assert!(tracker.press(after(start, 900)));
+}
+
+#[test]
+fn focus_lost_lets_go_of_a_hold() {
+    let mut app = app(Inspection::None);
+    let start = Instant::now();
+    app.press_space(start);
+    app.hold_step(after(start, 300));
+    assert_eq!(app.state, State::Ready);
+    app.focus_lost();
+    assert_eq!(app.state, State::Idle);
+    app.input_step(after(start, 1_000));
+    assert_eq!(app.state, State::Idle);
+}
+
+#[test]
+fn focus_lost_while_timing_warns() {
+    let mut app = app(Inspection::None);
+    let start = Instant::now();
+    app.press_space(start);
+    app.release_space(after(start, 300));
+    app.focus_lost();
+    assert_eq!(app.state, State::Timing);
+    assert!(app.message.is_some());
+
+    // nor with space read from the keyboard, as it is ignored out of focus
+    app.message = None;
+    app.input = InputMode::Evdev;
+    app.focus_lost();
+    assert!(app.message.is_some());
+}
@@ -0,0 +1,39 @@
+use cube_timer::app::Penalty;
+use cube_timer::scramble::Variant;
+use cube_timer::times::Times;
+
+mod common;
+
+#[test]
+fn events_keep_their_own_times_and_bests() {
+    let mut times = Times::in_memory();
+    times.add_time(12.0, String::new(), Penalty::None);
+    times.add_time(11.0, String::new(), Penalty::None);
+
+    times.set_variant(Variant::OneHanded);
+    assert_eq!(times.num_times(), 0);
+    assert_eq!(times.bests()[0], "NA");
+    times.add_time(25.0, String::new(), Penalty::None);
+    assert_eq!(times.bests()[0], "25.000");
+
+    times.set_variant(Variant::ThreeByThree);
+    assert_eq!(times.num_times(), 2);
+    assert_eq!(times.bests()[0], "11.000");
+    assert_eq!(times.currents()[0], "11.000");
+
+    times.set_variant(Variant::OneHanded);
+    assert_eq!(times.num_times(), 1);
+    assert_eq!(times.currents()[0], "25.000");
+}
+
+#[test]
+fn switching_event_shows_its_times() {
+    let mut app = common::app();
+    app.times.add_time(12.0, String::new(), Penalty::None);
+    app.table_state.select(Some(0));
+
+    app.next_variant();
+    assert_eq!(app.variant, Variant::OneHanded);
+    assert_eq!(app.times.num_times(), 0);
+    assert_eq!(app.table_state.selected(), None);
+}